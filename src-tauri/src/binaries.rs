@@ -0,0 +1,207 @@
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+use tauri::Manager;
+
+const YT_DLP_RELEASE_BASE: &str = "https://github.com/yt-dlp/yt-dlp/releases/latest/download";
+
+fn yt_dlp_asset_name() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "yt-dlp.exe"
+    } else if cfg!(target_os = "macos") {
+        "yt-dlp_macos"
+    } else {
+        "yt-dlp_linux"
+    }
+}
+
+fn ffmpeg_binary_name() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "ffmpeg.exe"
+    } else {
+        "ffmpeg"
+    }
+}
+
+/// `ManifestEntry::id` the ffmpeg signed manifest uses for this platform.
+/// `eugeneware/ffmpeg-static` doesn't publish per-binary checksums on GitHub
+/// Releases at all, so ffmpeg can't be verified the way yt-dlp is (against
+/// an upstream-published hash) — instead [`install_or_update_ffmpeg`] fetches
+/// a first-party signed manifest, the same ed25519 trust anchor
+/// `updater.rs` already uses for models/tools.
+fn ffmpeg_manifest_entry_id() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "ffmpeg-windows-x64"
+    } else if cfg!(target_os = "macos") {
+        "ffmpeg-macos-x64"
+    } else {
+        "ffmpeg-linux-x64"
+    }
+}
+
+/// App-local directory self-managed binaries are installed into, so the app
+/// works out-of-the-box without a system yt-dlp/ffmpeg install.
+fn bin_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let mut dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|err| format!("Unable to resolve the app data directory: {err}"))?;
+    dir.push("bin");
+    std::fs::create_dir_all(&dir)
+        .map_err(|err| format!("Failed to create app bin directory: {err}"))?;
+    Ok(dir)
+}
+
+/// Path to use for `yt-dlp`: the app-managed copy if one has been
+/// installed, otherwise the bare command name so it's resolved from PATH.
+pub fn resolve_yt_dlp(app: &tauri::AppHandle) -> PathBuf {
+    resolve_or_fallback(app, yt_dlp_asset_name(), "yt-dlp")
+}
+
+/// Path to use for `ffmpeg`, following the same app-managed-first fallback
+/// as [`resolve_yt_dlp`].
+pub fn resolve_ffmpeg(app: &tauri::AppHandle) -> PathBuf {
+    resolve_or_fallback(app, ffmpeg_binary_name(), "ffmpeg")
+}
+
+fn resolve_or_fallback(app: &tauri::AppHandle, managed_name: &str, path_name: &str) -> PathBuf {
+    if let Ok(dir) = bin_dir(app) {
+        let candidate = dir.join(managed_name);
+        if candidate.exists() {
+            return candidate;
+        }
+    }
+    PathBuf::from(path_name)
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    hex::encode(hasher.finalize())
+}
+
+/// Write `bytes` to `dest`, marking the result executable on Unix. Shared by
+/// every binary-provisioning path once its bytes have already been verified
+/// (by checksum or by signature), so only one place pokes at file permissions.
+async fn write_executable(dest: &Path, bytes: &[u8]) -> Result<(), String> {
+    tokio::fs::write(dest, bytes)
+        .await
+        .map_err(|err| format!("Failed to write {}: {err}", dest.display()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(dest)
+            .map_err(|err| err.to_string())?
+            .permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(dest, perms).map_err(|err| err.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Download `url`, verify its SHA-256 against `expected_sha256`, and write
+/// the result to `dest` (marking it executable on Unix). The published
+/// checksum is the tamper/corruption check; we never trust bytes that
+/// don't match it.
+async fn download_and_verify(url: &str, expected_sha256: &str, dest: &Path) -> Result<(), String> {
+    let bytes = reqwest::get(url)
+        .await
+        .map_err(|err| format!("Failed to download {url}: {err}"))?
+        .bytes()
+        .await
+        .map_err(|err| format!("Failed to read response body from {url}: {err}"))?;
+
+    let digest = sha256_hex(&bytes);
+    if !digest.eq_ignore_ascii_case(expected_sha256.trim()) {
+        return Err(format!(
+            "Checksum mismatch for {url}: expected {expected_sha256}, got {digest}"
+        ));
+    }
+
+    write_executable(dest, &bytes).await
+}
+
+/// Look up `asset`'s hash in a combined checksums manifest — one
+/// `<hex-digest>  <filename>` line per released asset, optionally prefixed
+/// with `*` for binary mode, the format yt-dlp's GitHub releases actually
+/// publish (`SHA2-256SUMS`) rather than a per-asset `.sha256` sidecar.
+fn find_checksum<'a>(checksums_text: &'a str, asset: &str) -> Option<&'a str> {
+    checksums_text.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        let hash = parts.next()?;
+        let name = parts.next()?.trim_start_matches('*');
+        (name == asset).then_some(hash)
+    })
+}
+
+/// Download the latest yt-dlp release asset for this platform into the
+/// app-local bin directory, verifying its SHA-256 against the combined
+/// `SHA2-256SUMS` manifest yt-dlp's GitHub releases publish. Returns the
+/// resolved path on success. Used by both the first-run setup and the
+/// `update_ytdlp` command.
+pub async fn install_or_update_yt_dlp(app: &tauri::AppHandle) -> Result<String, String> {
+    let dir = bin_dir(app)?;
+    let asset = yt_dlp_asset_name();
+    let url = format!("{YT_DLP_RELEASE_BASE}/{asset}");
+    let checksums_url = format!("{YT_DLP_RELEASE_BASE}/SHA2-256SUMS");
+
+    let checksums_text = reqwest::get(&checksums_url)
+        .await
+        .map_err(|err| format!("Failed to fetch checksums from {checksums_url}: {err}"))?
+        .text()
+        .await
+        .map_err(|err| format!("Failed to read checksums body: {err}"))?;
+    let expected = find_checksum(&checksums_text, asset)
+        .ok_or_else(|| format!("No checksum entry for {asset} in {checksums_url}"))?;
+
+    let dest = dir.join(asset);
+    download_and_verify(&url, expected, &dest).await?;
+
+    dest.to_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| "Unable to convert binary path to string".to_string())
+}
+
+/// Download a static ffmpeg build for this platform into the app-local bin
+/// directory, verifying it against a first-party signed manifest rather than
+/// an upstream checksum `eugeneware/ffmpeg-static` doesn't publish. Returns
+/// the resolved path on success. Used by the `update_ffmpeg` command, which
+/// takes the same caller-supplied `manifest_url` as `update_signed_assets`.
+pub async fn install_or_update_ffmpeg(app: &tauri::AppHandle, manifest_url: &str) -> Result<String, String> {
+    let dir = bin_dir(app)?;
+    let bytes = crate::updater::fetch_verified_entry(manifest_url, ffmpeg_manifest_entry_id()).await?;
+    let dest = dir.join(ffmpeg_binary_name());
+    write_executable(&dest, &bytes).await?;
+
+    dest.to_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| "Unable to convert binary path to string".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHECKSUMS: &str = "\
+abc123  yt-dlp_linux
+def456  yt-dlp_macos
+*789xyz  yt-dlp.exe
+";
+
+    #[test]
+    fn find_checksum_matches_plain_filename() {
+        assert_eq!(find_checksum(CHECKSUMS, "yt-dlp_linux"), Some("abc123"));
+    }
+
+    #[test]
+    fn find_checksum_strips_binary_mode_prefix() {
+        assert_eq!(find_checksum(CHECKSUMS, "yt-dlp.exe"), Some("789xyz"));
+    }
+
+    #[test]
+    fn find_checksum_returns_none_for_unknown_asset() {
+        assert_eq!(find_checksum(CHECKSUMS, "yt-dlp_freebsd"), None);
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use rayon::prelude::*;
+use serde::Serialize;
+
+const PREFIX_LEN: usize = 8 * 1024;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateGroup {
+    pub paths: Vec<String>,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct CacheReport {
+    pub total_size: u64,
+    pub duplicate_groups: Vec<DuplicateGroup>,
+    pub reclaimable_bytes: u64,
+}
+
+fn walk(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            walk(&path, out);
+        } else {
+            out.push(path);
+        }
+    }
+}
+
+fn prefix_hash(path: &Path) -> Option<u64> {
+    let mut file = File::open(path).ok()?;
+    let mut buf = vec![0u8; PREFIX_LEN];
+    let n = file.read(&mut buf).ok()?;
+    Some(xxhash_rust::xxh3::xxh3_64(&buf[..n]))
+}
+
+fn full_hash(path: &Path) -> Option<String> {
+    let bytes = std::fs::read(path).ok()?;
+    Some(blake3::hash(&bytes).to_hex().to_string())
+}
+
+/// Walk `dirs` in parallel (rayon) and find duplicate files using a
+/// czkawka-style staged filter: group candidates by exact byte length first
+/// (a unique size can't have a duplicate), split each size-group further by
+/// a fast hash of the first 8 KiB, then confirm remaining collisions with a
+/// full-file hash. Only files that survive all three stages are ever fully
+/// read, so scanning a cache full of distinct large files stays cheap.
+pub fn analyze(dirs: &[PathBuf]) -> CacheReport {
+    let mut all_files = Vec::new();
+    for dir in dirs {
+        walk(dir, &mut all_files);
+    }
+
+    let total_size: u64 = all_files
+        .par_iter()
+        .filter_map(|path| std::fs::metadata(path).ok())
+        .map(|metadata| metadata.len())
+        .sum();
+
+    let mut by_size: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+    for path in &all_files {
+        if let Ok(metadata) = std::fs::metadata(path) {
+            by_size.entry(metadata.len()).or_default().push(path.clone());
+        }
+    }
+
+    let mut duplicate_groups = Vec::new();
+    let mut reclaimable_bytes = 0u64;
+
+    for (size, candidates) in by_size {
+        if size == 0 || candidates.len() < 2 {
+            continue;
+        }
+
+        let prefixed: Vec<(u64, PathBuf)> = candidates
+            .par_iter()
+            .filter_map(|path| prefix_hash(path).map(|hash| (hash, path.clone())))
+            .collect();
+
+        let mut by_prefix: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+        for (hash, path) in prefixed {
+            by_prefix.entry(hash).or_default().push(path);
+        }
+
+        for group in by_prefix.into_values() {
+            if group.len() < 2 {
+                continue;
+            }
+
+            let hashed: Vec<(String, PathBuf)> = group
+                .par_iter()
+                .filter_map(|path| full_hash(path).map(|digest| (digest, path.clone())))
+                .collect();
+
+            let mut by_full: HashMap<String, Vec<PathBuf>> = HashMap::new();
+            for (digest, path) in hashed {
+                by_full.entry(digest).or_default().push(path);
+            }
+
+            for paths in by_full.into_values() {
+                if paths.len() < 2 {
+                    continue;
+                }
+                reclaimable_bytes += size * (paths.len() as u64 - 1);
+                duplicate_groups.push(DuplicateGroup {
+                    paths: paths.iter().map(|p| p.to_string_lossy().to_string()).collect(),
+                    size,
+                });
+            }
+        }
+    }
+
+    CacheReport {
+        total_size,
+        duplicate_groups,
+        reclaimable_bytes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn analyze_finds_duplicate_files_by_content() {
+        let dir = temp_dir("mediaflow_test_cache_analysis_dupes");
+        std::fs::write(dir.join("a.wav"), b"same bytes").unwrap();
+        std::fs::write(dir.join("b.wav"), b"same bytes").unwrap();
+        std::fs::write(dir.join("c.wav"), b"different").unwrap();
+
+        let report = analyze(&[dir.clone()]);
+
+        assert_eq!(report.duplicate_groups.len(), 1);
+        assert_eq!(report.duplicate_groups[0].paths.len(), 2);
+        assert_eq!(report.duplicate_groups[0].size, "same bytes".len() as u64);
+        assert_eq!(report.reclaimable_bytes, "same bytes".len() as u64);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn analyze_ignores_empty_files_and_unique_sizes() {
+        let dir = temp_dir("mediaflow_test_cache_analysis_unique");
+        std::fs::write(dir.join("empty1.log"), b"").unwrap();
+        std::fs::write(dir.join("empty2.log"), b"").unwrap();
+        std::fs::write(dir.join("unique.json"), b"{}").unwrap();
+
+        let report = analyze(&[dir.clone()]);
+
+        assert!(report.duplicate_groups.is_empty());
+        assert_eq!(report.reclaimable_bytes, 0);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn analyze_sums_total_size_across_all_files() {
+        let dir = temp_dir("mediaflow_test_cache_analysis_total");
+        std::fs::write(dir.join("one.wav"), b"12345").unwrap();
+        std::fs::write(dir.join("two.wav"), b"67").unwrap();
+
+        let report = analyze(&[dir.clone()]);
+
+        assert_eq!(report.total_size, 7);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
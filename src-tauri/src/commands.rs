@@ -1,18 +1,23 @@
 use chrono::Utc;
-use dirs::download_dir;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
+
+use crate::jobs::JobId;
 use std::{
     fs,
     path::PathBuf,
     process::{Command, Output},
 };
+use tauri::Manager;
 use tauri_plugin_dialog::DialogExt;
 
 /// Get the Python command that works on this system ("python" or "py" on Windows)
 fn get_python_command() -> &'static str {
     // Try "python" first
-    if let Ok(output) = Command::new("python").arg("--version").output() {
+    let mut cmd = Command::new("python");
+    cmd.arg("--version");
+    suppress_console(&mut cmd);
+    if let Ok(output) = cmd.output() {
         if output.status.success() {
             return "python";
         }
@@ -21,10 +26,26 @@ fn get_python_command() -> &'static str {
     "py"
 }
 
+/// Suppress the console window Windows would otherwise flash for every
+/// spawned yt-dlp/ffmpeg/python child — the same CREATE_NO_WINDOW flag the
+/// `youtube_dl` crate applies to its subprocesses. A no-op elsewhere.
+#[cfg(target_os = "windows")]
+fn suppress_console(cmd: &mut Command) {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+    cmd.creation_flags(CREATE_NO_WINDOW);
+}
+
+#[cfg(not(target_os = "windows"))]
+fn suppress_console(_cmd: &mut Command) {}
+
 /// Get Python 3.11 command for demucs (requires older Python)
 fn get_python311_command() -> Vec<String> {
     // Try py -3.11 first (Windows Python Launcher)
-    if let Ok(output) = Command::new("py").args(&["-3.11", "--version"]).output() {
+    let mut cmd = Command::new("py");
+    cmd.args(&["-3.11", "--version"]);
+    suppress_console(&mut cmd);
+    if let Ok(output) = cmd.output() {
         if output.status.success() {
             return vec!["py".to_string(), "-3.11".to_string()];
         }
@@ -57,16 +78,60 @@ struct VideoInfoPayload {
 struct DownloadPayload {
     filename: String,
     path: String,
+    thumbnail_path: Option<String>,
+    subtitle_paths: Vec<String>,
 }
 
-fn mediaflow_download_dir() -> Result<PathBuf, String> {
-    let mut base = download_dir().ok_or("Unable to locate the system Downloads directory")?;
+/// Subtitle selection for `download_audio`/`download_video`: which languages
+/// to pull and whether to fall back to YouTube's auto-generated captions
+/// when no authored track exists.
+#[derive(Deserialize, Clone, Default)]
+struct SubtitleOpts {
+    langs: Vec<String>,
+    #[serde(default)]
+    auto: bool,
+}
+
+/// Where finished downloads/uploads land: the OS's standard Downloads
+/// folder (resolved through Tauri's `PathResolver` so it works the same in
+/// packaged builds on every platform), under a `MediaFlow` subfolder.
+fn mediaflow_download_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let mut base = app
+        .path()
+        .download_dir()
+        .map_err(|err| format!("Unable to locate the system Downloads directory: {err}"))?;
     base.push("MediaFlow");
     fs::create_dir_all(&base)
         .map_err(|err| format!("Failed to create MediaFlow download folder: {err}"))?;
     Ok(base)
 }
 
+/// Where scratch/cache data (uploads, temp processing output) lives: the
+/// app's own cache directory, e.g. `~/.cache/<id>` on Linux or
+/// `~/Library/Caches/<id>` on macOS.
+fn mediaflow_cache_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let base = app
+        .path()
+        .app_cache_dir()
+        .map_err(|err| format!("Unable to resolve the app cache directory: {err}"))?;
+    fs::create_dir_all(&base)
+        .map_err(|err| format!("Failed to create app cache directory: {err}"))?;
+    Ok(base)
+}
+
+/// Where installed AI models live: the app's data directory, e.g.
+/// `~/.local/share/<id>` on Linux or `%APPDATA%\<id>` on Windows.
+fn mediaflow_models_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let mut base = app
+        .path()
+        .app_data_dir()
+        .map_err(|err| format!("Unable to resolve the app data directory: {err}"))?;
+    base.push("models");
+    fs::create_dir_all(&base)
+        .map_err(|err| format!("Failed to create app data directory: {err}"))?;
+    Ok(base)
+}
+
 fn timestamp_suffix() -> String {
     Utc::now().format("%Y%m%d%H%M%S").to_string()
 }
@@ -90,13 +155,245 @@ fn ensure_success(output: &Output) -> Result<(), String> {
     }
 }
 
-fn yt_dlp(args: &[String]) -> Result<Output, String> {
-    Command::new("yt-dlp")
-        .args(args)
-        .output()
+fn yt_dlp(app: &tauri::AppHandle, args: &[String]) -> Result<Output, String> {
+    let mut cmd = Command::new(crate::binaries::resolve_yt_dlp(app));
+    cmd.args(args);
+    suppress_console(&mut cmd);
+    cmd.output()
         .map_err(|err| format!("Failed to run yt-dlp. Is it installed and on PATH? {err}"))
 }
 
+#[derive(Serialize, Clone)]
+struct DownloadProgress {
+    downloaded: u64,
+    total: u64,
+    speed: f64,
+    eta: i64,
+    percent: f64,
+}
+
+/// Parse one line of yt-dlp's `--progress-template
+/// "%(progress.downloaded_bytes)s/%(progress.total_bytes)s/%(progress.speed)s/%(progress.eta)s"`
+/// output. Any field yt-dlp can't determine yet comes through as `"NA"`,
+/// which we treat as zero/unknown rather than failing the whole line.
+fn parse_progress_line(line: &str) -> Option<DownloadProgress> {
+    let mut fields = line.trim().splitn(4, '/');
+    let downloaded = fields.next()?.parse().unwrap_or(0);
+    let total = fields.next()?.parse().unwrap_or(0);
+    let speed = fields.next()?.parse().unwrap_or(0.0);
+    let eta = fields.next()?.parse().unwrap_or(-1);
+
+    let percent = if total > 0 {
+        (downloaded as f64 / total as f64) * 100.0
+    } else {
+        0.0
+    };
+
+    Some(DownloadProgress {
+        downloaded,
+        total,
+        speed,
+        eta,
+        percent,
+    })
+}
+
+/// Run yt-dlp with its stdout piped so per-line download progress can be
+/// parsed and streamed to the frontend as `download-progress` events for
+/// `job_id`, instead of blocking silently until the whole download finishes.
+fn yt_dlp_streaming(app: &tauri::AppHandle, job_id: &str, args: &[String]) -> Result<(), String> {
+    use std::io::{BufRead, BufReader, Read};
+    use std::process::Stdio;
+    use std::sync::{Arc, Mutex};
+    use tauri::Emitter;
+
+    let mut full_args = vec![
+        "--newline".to_string(),
+        "--progress-template".to_string(),
+        "%(progress.downloaded_bytes)s/%(progress.total_bytes)s/%(progress.speed)s/%(progress.eta)s"
+            .to_string(),
+    ];
+    full_args.extend_from_slice(args);
+
+    let mut cmd = Command::new(crate::binaries::resolve_yt_dlp(app));
+    cmd.args(&full_args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    suppress_console(&mut cmd);
+    let child = cmd
+        .spawn()
+        .map_err(|err| format!("Failed to run yt-dlp. Is it installed and on PATH? {err}"))?;
+
+    // Tracked behind the job registry so `cancel_job` can kill this process
+    // outright rather than only flipping the cooperative cancellation token.
+    let child = Arc::new(Mutex::new(child));
+    app.state::<crate::jobs::JobRegistry>()
+        .track_child(job_id, child.clone());
+
+    let (stdout, stderr) = {
+        let mut guard = child.lock().unwrap();
+        (guard.stdout.take(), guard.stderr.take())
+    };
+
+    // Drain stderr on its own thread so a full pipe buffer can't deadlock
+    // the progress-reading loop below.
+    let stderr_reader = std::thread::spawn(move || {
+        let mut buf = String::new();
+        if let Some(mut pipe) = stderr {
+            let _ = pipe.read_to_string(&mut buf);
+        }
+        buf
+    });
+
+    if let Some(stdout) = stdout {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if let Some(progress) = parse_progress_line(&line) {
+                let _ = app.emit(
+                    "download-progress",
+                    json!({"id": job_id, "progress": progress}),
+                );
+            }
+        }
+    }
+
+    let status = child.lock().unwrap().wait();
+    app.state::<crate::jobs::JobRegistry>().untrack_child(job_id, &child);
+
+    let status = status.map_err(|err| format!("Failed to wait on yt-dlp: {err}"))?;
+    let stderr_text = stderr_reader.join().unwrap_or_default();
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "yt-dlp exited with code {:?}: {}",
+            status.code(),
+            stderr_text
+        ))
+    }
+}
+
+/// Spawn `cmd`, tracking the child with the job registry so `cancel_job` can
+/// kill it, and block until it exits. Mirrors `yt_dlp_streaming`'s
+/// spawn/track/wait shape for the other long-running subprocesses (ffmpeg,
+/// model downloads) that don't need line-by-line progress; see
+/// [`run_tracked_with_demucs_progress`] for the one that does.
+fn run_tracked(app: &tauri::AppHandle, job_id: &str, mut cmd: Command) -> Result<Output, String> {
+    use std::io::Read;
+    use std::process::Stdio;
+    use std::sync::{Arc, Mutex};
+
+    cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+    suppress_console(&mut cmd);
+    let child = cmd
+        .spawn()
+        .map_err(|err| format!("Failed to spawn process: {err}"))?;
+
+    let child = Arc::new(Mutex::new(child));
+    app.state::<crate::jobs::JobRegistry>()
+        .track_child(job_id, child.clone());
+
+    let (stdout_pipe, stderr_pipe) = {
+        let mut guard = child.lock().unwrap();
+        (guard.stdout.take(), guard.stderr.take())
+    };
+
+    let mut stdout = Vec::new();
+    if let Some(mut pipe) = stdout_pipe {
+        let _ = pipe.read_to_end(&mut stdout);
+    }
+    let mut stderr = Vec::new();
+    if let Some(mut pipe) = stderr_pipe {
+        let _ = pipe.read_to_end(&mut stderr);
+    }
+
+    let status = child.lock().unwrap().wait();
+    app.state::<crate::jobs::JobRegistry>().untrack_child(job_id, &child);
+
+    let status = status.map_err(|err| format!("Failed to wait on process: {err}"))?;
+    Ok(Output {
+        status,
+        stdout,
+        stderr,
+    })
+}
+
+/// Parse a `tqdm`-style progress line Demucs emits while separating stems,
+/// e.g. `"100%|██████████| 353.8/353.8 [01:23<00:00,  4.25seconds/s]"`.
+/// Returns the percent complete, or `None` for lines that aren't a progress
+/// update (the "Separating track ..." banner, warnings, etc.).
+fn parse_demucs_progress_line(line: &str) -> Option<f64> {
+    let (percent_str, _rest) = line.trim().split_once('%')?;
+    percent_str.trim().parse().ok()
+}
+
+/// Like [`run_tracked`], but for the stem-separator script specifically:
+/// `tqdm` (which Demucs uses for its progress bar) redraws one line in place
+/// with `\r` rather than emitting `\n`-terminated lines the way yt-dlp's
+/// `--newline` progress does, so this reads stderr byte-by-byte and treats
+/// either `\r` or `\n` as a line boundary, emitting each parsed line as
+/// `job://progress` for `job_id` instead of leaving it pinned at the 0%
+/// `spawn_tracked_job` emits once at start.
+fn run_tracked_with_demucs_progress(app: &tauri::AppHandle, job_id: &str, mut cmd: Command) -> Result<Output, String> {
+    use std::io::Read;
+    use std::process::Stdio;
+    use std::sync::{Arc, Mutex};
+    use tauri::Emitter;
+
+    cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+    suppress_console(&mut cmd);
+    let child = cmd
+        .spawn()
+        .map_err(|err| format!("Failed to spawn process: {err}"))?;
+
+    let child = Arc::new(Mutex::new(child));
+    app.state::<crate::jobs::JobRegistry>()
+        .track_child(job_id, child.clone());
+
+    let (stdout_pipe, stderr_pipe) = {
+        let mut guard = child.lock().unwrap();
+        (guard.stdout.take(), guard.stderr.take())
+    };
+
+    // Demucs' own progress goes to stderr; stdout just gets drained on its
+    // own thread so a full pipe buffer can't deadlock the loop below.
+    let stdout_reader = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = stdout_pipe {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        buf
+    });
+
+    let mut stderr = Vec::new();
+    if let Some(mut pipe) = stderr_pipe {
+        let mut line = Vec::new();
+        let mut byte = [0u8; 1];
+        while let Ok(1) = pipe.read(&mut byte) {
+            stderr.push(byte[0]);
+            if byte[0] == b'\r' || byte[0] == b'\n' {
+                if let Some(percent) = parse_demucs_progress_line(&String::from_utf8_lossy(&line)) {
+                    let _ = app.emit("job://progress", json!({"id": job_id, "percent": percent}));
+                }
+                line.clear();
+            } else {
+                line.push(byte[0]);
+            }
+        }
+    }
+
+    let status = child.lock().unwrap().wait();
+    app.state::<crate::jobs::JobRegistry>().untrack_child(job_id, &child);
+
+    let status = status.map_err(|err| format!("Failed to wait on process: {err}"))?;
+    let stdout = stdout_reader.join().unwrap_or_default();
+    Ok(Output {
+        status,
+        stdout,
+        stderr,
+    })
+}
+
 fn synthesize_info_from_value(value: serde_json::Value) -> Result<VideoInfoPayload, String> {
     let primary = if let Some(entries) = value.get("entries").and_then(|v| v.as_array()) {
         entries.first().cloned().unwrap_or(value)
@@ -167,10 +464,21 @@ fn synthesize_info_from_value(value: serde_json::Value) -> Result<VideoInfoPaylo
 }
 
 fn download_audio_sync(
+    app: &tauri::AppHandle,
+    job_id: &str,
     url: String,
     quality: Option<String>,
     format: Option<String>,
     download_path: Option<String>,
+    embed_metadata: bool,
+    embed_thumbnail: bool,
+    embed_chapters: bool,
+    subtitles: Option<SubtitleOpts>,
+    convert_subs: Option<String>,
+    cookies_from_browser: Option<String>,
+    cookies_file: Option<String>,
+    player_client: Option<Vec<String>>,
+    po_token: Option<String>,
 ) -> Result<String, String> {
     let dir = if let Some(path) = download_path {
         let p = PathBuf::from(path);
@@ -179,34 +487,58 @@ fn download_audio_sync(
         }
         p
     } else {
-        mediaflow_download_dir()?
+        mediaflow_download_dir(app)?
     };
     let requested_format = format.unwrap_or_else(|| "mp3".to_string());
-    
+
     // Create filename base without extension (yt-dlp will add it)
     let filename_base = format!(
         "mediaflow_audio_{}",
         timestamp_suffix()
     );
     let target_template = dir.join(&filename_base);
-    
+
     let mut quality_arg = quality.unwrap_or_else(|| "320".to_string());
     if quality_arg != "0" && !quality_arg.to_lowercase().ends_with('k') {
         quality_arg.push_str("K");
     }
 
-    let output = yt_dlp(&vec![
+    let (metadata_args, subtitle_paths) = build_metadata_args(
+        embed_metadata,
+        embed_thumbnail,
+        embed_chapters,
+        &subtitles,
+        &convert_subs,
+        &dir,
+        &filename_base,
+    );
+
+    let mut args = vec![
         "-x".into(),
         "--audio-format".into(),
         requested_format.clone(),
         "--audio-quality".into(),
         quality_arg,
+    ];
+    args.extend(metadata_args);
+    args.extend(build_auth_args(
+        &cookies_from_browser,
+        &cookies_file,
+        &player_client,
+        &po_token,
+    ));
+    args.extend([
         "-o".into(),
-        to_path_string(&target_template)?,  // No extension - yt-dlp adds it
+        to_path_string(&target_template)?, // No extension - yt-dlp adds it
         url,
-    ])?;
+    ]);
 
-    ensure_success(&output)?;
+    if let Err(err) = yt_dlp_streaming(app, job_id, &args) {
+        if app.state::<crate::jobs::JobRegistry>().is_cancelled(job_id) {
+            cleanup_partial_output(&dir, &filename_base);
+        }
+        return Err(err);
+    }
 
     // Find the actual downloaded file (yt-dlp adds the extension)
     let actual_filename = format!("{}.{}", filename_base, requested_format);
@@ -215,17 +547,239 @@ fn download_audio_sync(
     let payload = DownloadPayload {
         filename: actual_filename,
         path: to_path_string(&actual_path)?,
+        // The thumbnail is embedded into the audio container itself rather
+        // than kept as a sidecar file, so this just echoes the media path.
+        thumbnail_path: if embed_thumbnail {
+            Some(to_path_string(&actual_path)?)
+        } else {
+            None
+        },
+        subtitle_paths,
     };
     serde_json::to_string(&payload).map_err(|err| err.to_string())
 }
 
+/// Build a yt-dlp format selector that tries `preferred_codecs` in order
+/// (e.g. `["avc1", "vp9"]` to prefer H.264 over VP9) before falling back to
+/// the unconstrained best-quality selector, so playback isn't handed an
+/// AV1/HEVC/Opus rendition the user's downstream tooling can't decode.
+/// `exclude_codecs` is applied as a negative filter at every step.
+fn build_video_format_selector(
+    res: &str,
+    fps: i32,
+    include_audio: bool,
+    preferred_codecs: &[String],
+    exclude_codecs: &[String],
+) -> String {
+    let exclude_clause = |field: &str| -> String {
+        exclude_codecs
+            .iter()
+            .map(|codec| format!("[{field}!^={codec}]"))
+            .collect::<String>()
+    };
+
+    let selector_for = |vcodec_filter: String| -> String {
+        let video = format!("bestvideo{vcodec_filter}[height<={res}][fps<={fps}]{}", exclude_clause("vcodec"));
+        if include_audio {
+            format!("{video}+bestaudio{}", exclude_clause("acodec"))
+        } else {
+            video
+        }
+    };
+
+    let mut candidates: Vec<String> = preferred_codecs
+        .iter()
+        .map(|codec| selector_for(format!("[vcodec^={codec}]")))
+        .collect();
+
+    candidates.push(selector_for(String::new()));
+    if include_audio {
+        candidates.push(format!("best[height<={res}][fps<={fps}]"));
+    }
+
+    candidates.join("/")
+}
+
+/// Build the yt-dlp args for optional metadata/thumbnail/chapter embedding
+/// and subtitle download, plus the subtitle sidecar paths those args are
+/// expected to produce under `dir/filename_base`. Shared by
+/// `download_audio_sync` and `download_video_sync`.
+fn build_metadata_args(
+    embed_metadata: bool,
+    embed_thumbnail: bool,
+    embed_chapters: bool,
+    subtitles: &Option<SubtitleOpts>,
+    convert_subs: &Option<String>,
+    dir: &std::path::Path,
+    filename_base: &str,
+) -> (Vec<String>, Vec<String>) {
+    let mut args = Vec::new();
+    if embed_metadata {
+        args.push("--embed-metadata".into());
+    }
+    if embed_thumbnail {
+        args.push("--embed-thumbnail".into());
+    }
+    if embed_chapters {
+        args.push("--embed-chapters".into());
+    }
+
+    let mut subtitle_paths = Vec::new();
+    if let Some(opts) = subtitles {
+        args.push(if opts.auto {
+            "--write-auto-subs".into()
+        } else {
+            "--write-subs".into()
+        });
+        args.push("--sub-langs".into());
+        args.push(opts.langs.join(","));
+        args.push("--embed-subs".into());
+
+        if let Some(sub_format) = convert_subs {
+            args.push("--convert-subs".into());
+            args.push(sub_format.clone());
+        }
+
+        let ext = convert_subs.clone().unwrap_or_else(|| "vtt".to_string());
+        for lang in &opts.langs {
+            subtitle_paths.push(
+                dir.join(format!("{filename_base}.{lang}.{ext}"))
+                    .to_string_lossy()
+                    .to_string(),
+            );
+        }
+    }
+
+    (args, subtitle_paths)
+}
+
+/// Build yt-dlp args for cookie-based auth and the `youtube` extractor's
+/// `player_client`/`po_token` options, so age-gated, members-only, or
+/// bot-challenged videos can still be fetched. Shared by `get_video_info`,
+/// `download_audio_sync`, and `download_video_sync`.
+fn build_auth_args(
+    cookies_from_browser: &Option<String>,
+    cookies_file: &Option<String>,
+    player_client: &Option<Vec<String>>,
+    po_token: &Option<String>,
+) -> Vec<String> {
+    let mut args = Vec::new();
+
+    if let Some(browser) = cookies_from_browser {
+        args.push("--cookies-from-browser".into());
+        args.push(browser.clone());
+    }
+    if let Some(file) = cookies_file {
+        args.push("--cookies".into());
+        args.push(file.clone());
+    }
+
+    let mut youtube_args = Vec::new();
+    if let Some(clients) = player_client {
+        if !clients.is_empty() {
+            youtube_args.push(format!("player_client={}", clients.join(",")));
+        }
+    }
+    if let Some(token) = po_token {
+        youtube_args.push(format!("po_token={token}"));
+    }
+    if !youtube_args.is_empty() {
+        args.push("--extractor-args".into());
+        args.push(format!("youtube:{}", youtube_args.join(";")));
+    }
+
+    args
+}
+
+/// Detect which browsers yt-dlp's `--cookies-from-browser` can likely read
+/// cookies from on this machine, by checking for each one's profile
+/// directory. Surfaced through `check_dependencies` so the UI can offer a
+/// picker instead of free-text browser names.
+fn detect_cookie_browsers(app: &tauri::AppHandle) -> serde_json::Value {
+    let Ok(home) = app.path().home_dir() else {
+        return serde_json::json!([]);
+    };
+
+    let candidates: &[(&str, &[&str])] = &[
+        (
+            "chrome",
+            &[
+                ".config/google-chrome",
+                "Library/Application Support/Google/Chrome",
+                "AppData/Local/Google/Chrome/User Data",
+            ],
+        ),
+        (
+            "firefox",
+            &[
+                ".mozilla/firefox",
+                "Library/Application Support/Firefox",
+                "AppData/Roaming/Mozilla/Firefox",
+            ],
+        ),
+        (
+            "edge",
+            &[
+                ".config/microsoft-edge",
+                "Library/Application Support/Microsoft Edge",
+                "AppData/Local/Microsoft/Edge/User Data",
+            ],
+        ),
+        (
+            "brave",
+            &[
+                ".config/BraveSoftware/Brave-Browser",
+                "Library/Application Support/BraveSoftware/Brave-Browser",
+                "AppData/Local/BraveSoftware/Brave-Browser/User Data",
+            ],
+        ),
+    ];
+
+    let browsers: Vec<serde_json::Value> = candidates
+        .iter()
+        .map(|(name, profile_paths)| {
+            let available = profile_paths.iter().any(|path| home.join(path).exists());
+            serde_json::json!({"name": name, "available": available})
+        })
+        .collect();
+
+    serde_json::json!(browsers)
+}
+
+/// Best-effort cleanup of whatever yt-dlp left behind under `filename_base`
+/// (the final file, `.part`/`.ytdl` fragments, subtitle sidecars) after a
+/// job is killed mid-download via `cancel_job`.
+fn cleanup_partial_output(dir: &std::path::Path, filename_base: &str) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if entry.file_name().to_string_lossy().starts_with(filename_base) {
+            let _ = fs::remove_file(entry.path());
+        }
+    }
+}
+
 fn download_video_sync(
+    app: &tauri::AppHandle,
+    job_id: &str,
     url: String,
     resolution: Option<String>,
     include_audio: Option<bool>,
     fps: Option<i32>,
     container: Option<String>,
     download_path: Option<String>,
+    preferred_codecs: Vec<String>,
+    exclude_codecs: Vec<String>,
+    embed_metadata: bool,
+    embed_thumbnail: bool,
+    embed_chapters: bool,
+    subtitles: Option<SubtitleOpts>,
+    convert_subs: Option<String>,
+    cookies_from_browser: Option<String>,
+    cookies_file: Option<String>,
+    player_client: Option<Vec<String>>,
+    po_token: Option<String>,
 ) -> Result<String, String> {
     let dir = if let Some(path) = download_path {
         let p = PathBuf::from(path);
@@ -234,39 +788,72 @@ fn download_video_sync(
         }
         p
     } else {
-        mediaflow_download_dir()?
+        mediaflow_download_dir(app)?
     };
     let res = resolution.unwrap_or_else(|| "1080".to_string());
     let fps = fps.unwrap_or(30);
     let include_audio = include_audio.unwrap_or(true);
     let container = container.unwrap_or_else(|| "mp4".to_string());
 
-    let filename = format!("mediaflow_video_{}.{}", timestamp_suffix(), container);
+    let filename_base = format!("mediaflow_video_{}", timestamp_suffix());
+    let filename = format!("{filename_base}.{container}");
     let target = dir.join(&filename);
 
-    let video_selector = format!("bestvideo[height<={res}][fps<={fps}]");
-    let format_selector = if include_audio {
-        format!("{video_selector}+bestaudio/best[height<={res}][fps<={fps}]")
-    } else {
-        video_selector
-    };
+    let format_selector = build_video_format_selector(
+        &res,
+        fps,
+        include_audio,
+        &preferred_codecs,
+        &exclude_codecs,
+    );
 
-    let args = vec![
+    let (metadata_args, subtitle_paths) = build_metadata_args(
+        embed_metadata,
+        embed_thumbnail,
+        embed_chapters,
+        &subtitles,
+        &convert_subs,
+        &dir,
+        &filename_base,
+    );
+
+    let mut args = vec![
         "-f".into(),
         format_selector,
         "--merge-output-format".into(),
         container.clone(),
+    ];
+    args.extend(metadata_args);
+    args.extend(build_auth_args(
+        &cookies_from_browser,
+        &cookies_file,
+        &player_client,
+        &po_token,
+    ));
+    args.extend([
         "-o".into(),
         to_path_string(&target)?,
         url,
-    ];
+    ]);
 
-    let output = yt_dlp(&args)?;
-    ensure_success(&output)?;
+    if let Err(err) = yt_dlp_streaming(app, job_id, &args) {
+        if app.state::<crate::jobs::JobRegistry>().is_cancelled(job_id) {
+            cleanup_partial_output(&dir, &filename_base);
+        }
+        return Err(err);
+    }
 
     let payload = DownloadPayload {
         filename,
         path: to_path_string(&target)?,
+        // The thumbnail is embedded into the video container itself rather
+        // than kept as a sidecar file, so this just echoes the media path.
+        thumbnail_path: if embed_thumbnail {
+            Some(to_path_string(&target)?)
+        } else {
+            None
+        },
+        subtitle_paths,
     };
     serde_json::to_string(&payload).map_err(|err| err.to_string())
 }
@@ -304,22 +891,30 @@ fn find_scripts_dir() -> Result<PathBuf, String> {
     Err("Scripts directory not found. Make sure 'scripts/audio_analyzer.py' exists.".to_string())
 }
 
-fn detect_tempo_sync(audio_path: String) -> Result<String, String> {
+fn detect_tempo_sync(app: &tauri::AppHandle, audio_path: String) -> Result<String, String> {
     // Check if file exists
     if !PathBuf::from(&audio_path).exists() {
         return Err(format!("Audio file not found: {}", audio_path));
     }
 
+    // Held until this function returns so `compact_cache` can't compress the
+    // WAV out from under the Python script reading it by literal path below.
+    let _reference = app
+        .state::<crate::jobs::JobRegistry>()
+        .reference_path(&PathBuf::from(&audio_path));
+
     let scripts_dir = find_scripts_dir()?;
     let script_path = scripts_dir.join("audio_analyzer.py");
     
     // Call Python script for tempo detection
-    let output = Command::new(get_python_command())
-        .args(&[
-            script_path.to_str().ok_or("Invalid script path")?,
-            "tempo",
-            &audio_path
-        ])
+    let mut cmd = Command::new(get_python_command());
+    cmd.args(&[
+        script_path.to_str().ok_or("Invalid script path")?,
+        "tempo",
+        &audio_path,
+    ]);
+    suppress_console(&mut cmd);
+    let output = cmd
         .output()
         .map_err(|err| format!("Failed to run audio analyzer: {}. Make sure Python and pydub are installed.", err))?;
 
@@ -333,22 +928,30 @@ fn detect_tempo_sync(audio_path: String) -> Result<String, String> {
         .map_err(|err| format!("Failed to parse output: {}", err))
 }
 
-fn detect_key_sync(audio_path: String) -> Result<String, String> {
+fn detect_key_sync(app: &tauri::AppHandle, audio_path: String) -> Result<String, String> {
     // Check if file exists
     if !PathBuf::from(&audio_path).exists() {
         return Err(format!("Audio file not found: {}", audio_path));
     }
 
+    // Held until this function returns so `compact_cache` can't compress the
+    // WAV out from under the Python script reading it by literal path below.
+    let _reference = app
+        .state::<crate::jobs::JobRegistry>()
+        .reference_path(&PathBuf::from(&audio_path));
+
     let scripts_dir = find_scripts_dir()?;
     let script_path = scripts_dir.join("audio_analyzer.py");
     
     // Call Python script for key detection
-    let output = Command::new(get_python_command())
-        .args(&[
-            script_path.to_str().ok_or("Invalid script path")?,
-            "key",
-            &audio_path
-        ])
+    let mut cmd = Command::new(get_python_command());
+    cmd.args(&[
+        script_path.to_str().ok_or("Invalid script path")?,
+        "key",
+        &audio_path,
+    ]);
+    suppress_console(&mut cmd);
+    let output = cmd
         .output()
         .map_err(|err| format!("Failed to run audio analyzer: {}. Make sure Python and pydub are installed.", err))?;
 
@@ -363,6 +966,8 @@ fn detect_key_sync(audio_path: String) -> Result<String, String> {
 }
 
 fn pitch_shift_sync(
+    app: &tauri::AppHandle,
+    job_id: &str,
     input_path: String,
     output_path: Option<String>,
     semitones: f32,
@@ -371,7 +976,11 @@ fn pitch_shift_sync(
     if !source.exists() {
         return Err("Input file not found".to_string());
     }
-    
+
+    // Held for the whole call so `compact_cache` can't compress the source
+    // WAV out from under the ffmpeg invocation below.
+    let _reference = app.state::<crate::jobs::JobRegistry>().reference_path(&source);
+
     let semitones_i32 = semitones.round() as i32;
     let pitch_suffix = if semitones_i32 >= 0 {
         format!("_pitch+{}", semitones_i32)
@@ -419,12 +1028,16 @@ fn pitch_shift_sync(
 
     args.push(to_path_string(&destination)?);
 
-    let output = Command::new("ffmpeg")
-        .args(&args)
-        .output()
-        .map_err(|err| format!("Failed to run ffmpeg. Is it installed and in PATH? {}", err))?;
+    let mut cmd = Command::new(crate::binaries::resolve_ffmpeg(app));
+    cmd.args(&args);
+    let output = run_tracked(app, job_id, cmd)?;
 
     if !output.status.success() {
+        // Killed mid-encode via `cancel_job`: drop the truncated output
+        // file rather than leaving a partial, unplayable file behind.
+        if app.state::<crate::jobs::JobRegistry>().is_cancelled(job_id) {
+            let _ = fs::remove_file(&destination);
+        }
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(format!("FFmpeg pitch shift failed: {}", stderr));
     }
@@ -438,6 +1051,8 @@ fn pitch_shift_sync(
 }
 
 fn separate_stems_sync(
+    app: &tauri::AppHandle,
+    job_id: &str,
     input_path: String,
     output_dir: Option<String>,
     stems: u8,
@@ -449,7 +1064,11 @@ fn separate_stems_sync(
     if !source.exists() {
         return Err("Input file not found".to_string());
     }
-    
+
+    // Held for the whole call so `compact_cache` can't compress the source
+    // WAV out from under the stem-separation script below.
+    let _reference = app.state::<crate::jobs::JobRegistry>().reference_path(&source);
+
     // Validate stems count
     if stems != 2 && stems != 4 {
         return Err("Stems must be 2 or 4".to_string());
@@ -462,7 +1081,7 @@ fn separate_stems_sync(
             .unwrap_or("output");
         source.with_file_name(format!("{stem}_stems"))
     });
-    
+
     let scripts_dir = find_scripts_dir()?;
     let script_path = scripts_dir.join("stem_separator.py");
     
@@ -481,19 +1100,23 @@ fn separate_stems_sync(
     let gpu_str = if use_gpu.unwrap_or(true) { "true" } else { "false" };
     
     // Call Python script
-    let output = cmd
-        .args(&[
-            script_path.to_str().ok_or("Invalid script path")?,
-            &input_path,
-            target_dir.to_str().ok_or("Invalid output path")?,
-            &stems.to_string(),
-            &model,
-            gpu_str
-        ])
-        .output()
-        .map_err(|err| format!("Failed to run stem separator: {}. Make sure Python 3.11 and audio-separator are installed.", err))?;
+    cmd.args(&[
+        script_path.to_str().ok_or("Invalid script path")?,
+        &input_path,
+        target_dir.to_str().ok_or("Invalid output path")?,
+        &stems.to_string(),
+        &model,
+        gpu_str,
+    ]);
+    let output = run_tracked_with_demucs_progress(app, job_id, cmd)?;
 
     if !output.status.success() {
+        // Killed mid-separation via `cancel_job`: the stem output directory
+        // is dedicated to this job's result, so drop whatever partial stem
+        // files the script had written rather than leaving them behind.
+        if app.state::<crate::jobs::JobRegistry>().is_cancelled(job_id) {
+            let _ = fs::remove_dir_all(&target_dir);
+        }
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(format!("Stem separation failed: {}", stderr));
     }
@@ -503,21 +1126,94 @@ fn separate_stems_sync(
         .map_err(|err| format!("Failed to parse output: {}", err))
 }
 
+/// Re-download the latest yt-dlp release asset for this platform into the
+/// app-local bin directory, verifying its published checksum. Also used on
+/// first run to provision yt-dlp when no system install is found.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn update_ytdlp(app: tauri::AppHandle) -> Result<String, String> {
+    crate::binaries::install_or_update_yt_dlp(&app).await
+}
+
+/// Download a static ffmpeg build for this platform into the app-local bin
+/// directory, verifying it against the signed manifest at `manifest_url`
+/// (the same first-party trust anchor `update_signed_assets` uses), since
+/// upstream doesn't publish its own per-binary checksums. Mirrors
+/// [`update_ytdlp`] so ffmpeg is self-managed the same way yt-dlp is,
+/// instead of only ever being resolved from whatever happens to already be
+/// on PATH.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn update_ffmpeg(app: tauri::AppHandle, manifest_url: String) -> Result<String, String> {
+    crate::binaries::install_or_update_ffmpeg(&app, &manifest_url).await
+}
+
+/// Register an absolute path with the [`crate::media_protocol::MediaKeyStore`]
+/// and return the opaque key the frontend can use as `mediaflow://<key>` in
+/// an `<audio>`/`<video>` `src`, without ever seeing the real filesystem path.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn register_media_source(
+    app: tauri::AppHandle,
+    file_path: String,
+) -> Result<String, String> {
+    use tauri::Manager;
+
+    let path = PathBuf::from(&file_path);
+    if !path.exists() {
+        return Err(format!("File not found: {}", file_path));
+    }
+    let store = app.state::<std::sync::Arc<crate::media_protocol::MediaKeyStore>>();
+    Ok(store.register(path))
+}
+
+/// Resolve video metadata and formats, preferring the yt-dlp/Python path and
+/// falling back to the pure-Rust [`crate::innertube`] client (no external
+/// dependency required) if that probe is missing or fails. Both paths
+/// return the same `VideoInfoPayload` JSON shape so the frontend doesn't
+/// need to know which one answered.
 #[tauri::command]
-pub async fn get_video_info(url: String) -> Result<String, String> {
-    tauri::async_runtime::spawn_blocking(move || {
-        let output = yt_dlp(&vec!["-J".into(), url])?;
+pub async fn get_video_info(
+    app: tauri::AppHandle,
+    url: String,
+    cookies_from_browser: Option<String>,
+    cookies_file: Option<String>,
+    player_client: Option<Vec<String>>,
+    po_token: Option<String>,
+) -> Result<String, String> {
+    let fallback_app = app.clone();
+    let fallback_url = url.clone();
+
+    let yt_dlp_result = tauri::async_runtime::spawn_blocking(move || {
+        let mut args = vec!["-J".into()];
+        args.extend(build_auth_args(
+            &cookies_from_browser,
+            &cookies_file,
+            &player_client,
+            &po_token,
+        ));
+        args.push(url);
+
+        let output = yt_dlp(&app, &args)?;
         ensure_success(&output)?;
         let parsed: serde_json::Value =
             serde_json::from_slice(&output.stdout).map_err(|err| err.to_string())?;
-        let info = synthesize_info_from_value(parsed)?;
-        serde_json::to_string(&info).map_err(|err| err.to_string())
+        synthesize_info_from_value(parsed)
     })
     .await
-    .map_err(|err| err.to_string())?
+    .map_err(|err| err.to_string())?;
+
+    match yt_dlp_result {
+        Ok(info) => serde_json::to_string(&info).map_err(|err| err.to_string()),
+        Err(yt_dlp_err) => {
+            let info = crate::innertube::resolve_video_info(&fallback_app, &fallback_url)
+                .await
+                .map_err(|fallback_err| {
+                    format!("yt-dlp failed ({yt_dlp_err}) and the native fallback also failed: {fallback_err}")
+                })?;
+            serde_json::to_string(&info).map_err(|err| err.to_string())
+        }
+    }
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 struct PlaylistItem {
     url: String,
     title: String,
@@ -531,101 +1227,462 @@ struct PlaylistInfo {
     items: Vec<PlaylistItem>,
 }
 
-#[tauri::command]
-pub async fn get_playlist_info(url: String) -> Result<String, String> {
-    tauri::async_runtime::spawn_blocking(move || {
-        // Use --flat-playlist to get playlist items without downloading
-        let output = yt_dlp(&vec![
-            "-J".into(),
-            "--flat-playlist".into(),
-            url
-        ])?;
-        ensure_success(&output)?;
-        
-        let parsed: serde_json::Value =
-            serde_json::from_slice(&output.stdout).map_err(|err| err.to_string())?;
-        
-        let playlist_title = parsed
-            .get("title")
-            .and_then(|v| v.as_str())
-            .unwrap_or("Playlist")
-            .to_string();
-        
-        let mut items = Vec::new();
-        
-        if let Some(entries) = parsed.get("entries").and_then(|v| v.as_array()) {
-            for entry in entries {
-                let video_id = entry.get("id").and_then(|v| v.as_str()).unwrap_or("");
-                let video_url = entry.get("url").and_then(|v| v.as_str())
-                    .map(|s| s.to_string())
-                    .unwrap_or_else(|| {
-                        // Construct YouTube URL from video ID
-                        if !video_id.is_empty() {
-                            format!("https://www.youtube.com/watch?v={}", video_id)
-                        } else {
-                            String::new()
-                        }
-                    });
-                
-                if video_url.is_empty() {
-                    continue;
-                }
-                
-                let title = entry.get("title").and_then(|v| v.as_str()).unwrap_or("Unknown").to_string();
-                let duration = entry.get("duration").and_then(|v| v.as_f64()).unwrap_or(0.0);
-                
-                items.push(PlaylistItem {
-                    url: video_url,
-                    title,
-                    duration,
+/// Expand a playlist URL into its items via `--flat-playlist` (no per-video
+/// download). Shared by `get_playlist_info` and `download_playlist`.
+fn fetch_playlist_info(app: &tauri::AppHandle, url: String) -> Result<PlaylistInfo, String> {
+    let output = yt_dlp(app, &vec!["-J".into(), "--flat-playlist".into(), url])?;
+    ensure_success(&output)?;
+
+    let parsed: serde_json::Value =
+        serde_json::from_slice(&output.stdout).map_err(|err| err.to_string())?;
+
+    let playlist_title = parsed
+        .get("title")
+        .and_then(|v| v.as_str())
+        .unwrap_or("Playlist")
+        .to_string();
+
+    let mut items = Vec::new();
+
+    if let Some(entries) = parsed.get("entries").and_then(|v| v.as_array()) {
+        for entry in entries {
+            let video_id = entry.get("id").and_then(|v| v.as_str()).unwrap_or("");
+            let video_url = entry
+                .get("url")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| {
+                    // Construct YouTube URL from video ID
+                    if !video_id.is_empty() {
+                        format!("https://www.youtube.com/watch?v={}", video_id)
+                    } else {
+                        String::new()
+                    }
                 });
+
+            if video_url.is_empty() {
+                continue;
             }
+
+            let title = entry
+                .get("title")
+                .and_then(|v| v.as_str())
+                .unwrap_or("Unknown")
+                .to_string();
+            let duration = entry.get("duration").and_then(|v| v.as_f64()).unwrap_or(0.0);
+
+            items.push(PlaylistItem {
+                url: video_url,
+                title,
+                duration,
+            });
         }
-        
-        let info = PlaylistInfo {
-            title: playlist_title,
-            count: items.len(),
-            items,
-        };
-        
+    }
+
+    Ok(PlaylistInfo {
+        title: playlist_title,
+        count: items.len(),
+        items,
+    })
+}
+
+#[tauri::command]
+pub async fn get_playlist_info(app: tauri::AppHandle, url: String) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let info = fetch_playlist_info(&app, url)?;
         serde_json::to_string(&info).map_err(|err| err.to_string())
     })
     .await
     .map_err(|err| err.to_string())?
 }
 
+/// Spawn `work` on the blocking pool, registering a job id up front and
+/// emitting `job://progress`, then a terminal `job://done`/`job://error`
+/// (or `job://cancelled` if `cancel_job` fired before completion). The job
+/// id is returned synchronously so the caller can track or cancel it.
+fn spawn_tracked_job<F>(app: tauri::AppHandle, work: F) -> JobId
+where
+    F: FnOnce(JobId) -> Result<String, String> + Send + 'static,
+{
+    use tauri::Emitter;
+    use tauri::Manager;
+
+    let registry = app.state::<crate::jobs::JobRegistry>();
+    let (job_id, token) = registry.register();
+    let job_id_for_task = job_id.clone();
+    let app_for_task = app.clone();
+
+    tauri::async_runtime::spawn(async move {
+        let _ = app_for_task.emit(
+            "job://progress",
+            json!({"id": job_id_for_task, "percent": 0}),
+        );
+
+        let job_id_for_work = job_id_for_task.clone();
+        let result = tauri::async_runtime::spawn_blocking(move || work(job_id_for_work))
+            .await
+            .map_err(|err| err.to_string())
+            .and_then(|res| res);
+
+        if token.is_cancelled() {
+            let _ = app_for_task.emit("job://cancelled", json!({"id": job_id_for_task}));
+        } else {
+            match result {
+                Ok(payload) => {
+                    let _ = app_for_task.emit(
+                        "job://done",
+                        json!({"id": job_id_for_task, "payload": payload}),
+                    );
+                }
+                Err(error) => {
+                    let _ = app_for_task.emit(
+                        "job://error",
+                        json!({"id": job_id_for_task, "error": error}),
+                    );
+                }
+            }
+        }
+
+        app_for_task
+            .state::<crate::jobs::JobRegistry>()
+            .remove(&job_id_for_task);
+    });
+
+    job_id
+}
+
+/// Cancel an in-flight job started by `download_audio`, `download_video`,
+/// `pitch_shift`, `separate_stems`, or `download_ai_models`. Kills the job's
+/// tracked child process outright (if one is currently running) and flips
+/// its cooperative cancellation token so the spawned task reports
+/// `job://cancelled` instead of `job://done`/`job://error`.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn cancel_job(app: tauri::AppHandle, job_id: String) -> Result<bool, String> {
+    use tauri::Manager;
+    Ok(app.state::<crate::jobs::JobRegistry>().cancel(&job_id))
+}
+
 #[tauri::command]
 pub async fn download_audio(
+    app: tauri::AppHandle,
     url: String,
     quality: Option<String>,
     format: Option<String>,
     download_path: Option<String>,
-) -> Result<String, String> {
-    tauri::async_runtime::spawn_blocking(move || download_audio_sync(url, quality, format, download_path))
-        .await
-        .map_err(|err| err.to_string())?
+    embed_metadata: Option<bool>,
+    embed_thumbnail: Option<bool>,
+    embed_chapters: Option<bool>,
+    subtitles: Option<SubtitleOpts>,
+    convert_subs: Option<String>,
+    cookies_from_browser: Option<String>,
+    cookies_file: Option<String>,
+    player_client: Option<Vec<String>>,
+    po_token: Option<String>,
+) -> Result<JobId, String> {
+    let worker_app = app.clone();
+    Ok(spawn_tracked_job(app, move |job_id| {
+        download_audio_sync(
+            &worker_app,
+            &job_id,
+            url,
+            quality,
+            format,
+            download_path,
+            embed_metadata.unwrap_or(false),
+            embed_thumbnail.unwrap_or(false),
+            embed_chapters.unwrap_or(false),
+            subtitles,
+            convert_subs,
+            cookies_from_browser,
+            cookies_file,
+            player_client,
+            po_token,
+        )
+    }))
 }
 
 #[tauri::command]
 pub async fn download_video(
+    app: tauri::AppHandle,
     url: String,
     resolution: Option<String>,
     include_audio: Option<bool>,
     fps: Option<i32>,
     container: Option<String>,
     download_path: Option<String>,
-) -> Result<String, String> {
-    tauri::async_runtime::spawn_blocking(move || {
-        download_video_sync(url, resolution, include_audio, fps, container, download_path)
+    preferred_codecs: Option<Vec<String>>,
+    exclude_codecs: Option<Vec<String>>,
+    embed_metadata: Option<bool>,
+    embed_thumbnail: Option<bool>,
+    embed_chapters: Option<bool>,
+    subtitles: Option<SubtitleOpts>,
+    convert_subs: Option<String>,
+    cookies_from_browser: Option<String>,
+    cookies_file: Option<String>,
+    player_client: Option<Vec<String>>,
+    po_token: Option<String>,
+) -> Result<JobId, String> {
+    let worker_app = app.clone();
+    Ok(spawn_tracked_job(app, move |job_id| {
+        download_video_sync(
+            &worker_app,
+            &job_id,
+            url,
+            resolution,
+            include_audio,
+            fps,
+            container,
+            download_path,
+            preferred_codecs.unwrap_or_default(),
+            exclude_codecs.unwrap_or_default(),
+            embed_metadata.unwrap_or(false),
+            embed_thumbnail.unwrap_or(false),
+            embed_chapters.unwrap_or(false),
+            subtitles,
+            convert_subs,
+            cookies_from_browser,
+            cookies_file,
+            player_client,
+            po_token,
+        )
+    }))
+}
+
+#[derive(Serialize, Clone)]
+struct PlaylistItemResult {
+    url: String,
+    title: String,
+    success: bool,
+    payload: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct PlaylistDownloadReport {
+    total: usize,
+    succeeded: usize,
+    failed: usize,
+    results: Vec<PlaylistItemResult>,
+}
+
+/// Expand `url` into its items, then drive at most `parallel` per-item
+/// downloads concurrently via `buffer_unordered`, emitting a
+/// `playlist-item://done`/`playlist-item://error` event per item as it
+/// finishes and returning an aggregate success/failure report.
+async fn download_playlist_async(
+    app: tauri::AppHandle,
+    job_id: JobId,
+    url: String,
+    parallel: usize,
+    limit: Option<usize>,
+    audio_only: bool,
+    quality: Option<String>,
+    format: Option<String>,
+    resolution: Option<String>,
+    include_audio: Option<bool>,
+    fps: Option<i32>,
+    container: Option<String>,
+    download_path: Option<String>,
+) -> Result<PlaylistDownloadReport, String> {
+    use futures::stream::{self, StreamExt};
+    use tauri::Emitter;
+
+    let info_app = app.clone();
+    let info = tauri::async_runtime::spawn_blocking(move || fetch_playlist_info(&info_app, url))
+        .await
+        .map_err(|err| err.to_string())??;
+
+    let mut items = info.items;
+    if let Some(limit) = limit {
+        items.truncate(limit);
+    }
+    let total = items.len();
+
+    let results = stream::iter(items.into_iter().map(|item| {
+        let app = app.clone();
+        let job_id = job_id.clone();
+        let quality = quality.clone();
+        let format = format.clone();
+        let resolution = resolution.clone();
+        let container = container.clone();
+        let download_path = download_path.clone();
+
+        async move {
+            // Checked before every item so a `cancel_job` call stops the
+            // playlist instead of the next queued item immediately taking
+            // the killed process's place.
+            if app.state::<crate::jobs::JobRegistry>().is_cancelled(&job_id) {
+                let result = PlaylistItemResult {
+                    url: item.url.clone(),
+                    title: item.title.clone(),
+                    success: false,
+                    payload: None,
+                    error: Some("Playlist download was cancelled".to_string()),
+                };
+                let _ = app.emit("playlist-item://error", json!({"id": job_id, "item": &result}));
+                return result;
+            }
+
+            let download_app = app.clone();
+            let item_url = item.url.clone();
+            let outcome = tauri::async_runtime::spawn_blocking(move || {
+                if audio_only {
+                    download_audio_sync(
+                        &download_app,
+                        &job_id,
+                        item_url,
+                        quality,
+                        format,
+                        download_path,
+                        false,
+                        false,
+                        false,
+                        None,
+                        None,
+                        None,
+                        None,
+                        None,
+                        None,
+                    )
+                } else {
+                    download_video_sync(
+                        &download_app,
+                        &job_id,
+                        item_url,
+                        resolution,
+                        include_audio,
+                        fps,
+                        container,
+                        download_path,
+                        Vec::new(),
+                        Vec::new(),
+                        false,
+                        false,
+                        false,
+                        None,
+                        None,
+                        None,
+                        None,
+                        None,
+                        None,
+                    )
+                }
+            })
+            .await
+            .map_err(|err| err.to_string())
+            .and_then(|res| res);
+
+            let result = match &outcome {
+                Ok(payload) => PlaylistItemResult {
+                    url: item.url.clone(),
+                    title: item.title.clone(),
+                    success: true,
+                    payload: Some(payload.clone()),
+                    error: None,
+                },
+                Err(error) => PlaylistItemResult {
+                    url: item.url.clone(),
+                    title: item.title.clone(),
+                    success: false,
+                    payload: None,
+                    error: Some(error.clone()),
+                },
+            };
+
+            let event = if result.success {
+                "playlist-item://done"
+            } else {
+                "playlist-item://error"
+            };
+            let _ = app.emit(event, json!({"id": job_id, "item": &result}));
+
+            result
+        }
+    }))
+    .buffer_unordered(parallel.max(1))
+    .collect::<Vec<_>>()
+    .await;
+
+    let succeeded = results.iter().filter(|r| r.success).count();
+    Ok(PlaylistDownloadReport {
+        total,
+        succeeded,
+        failed: total - succeeded,
+        results,
     })
-    .await
-    .map_err(|err| err.to_string())?
+}
+
+/// Download every item of a playlist, running at most `parallel` (default
+/// 8) downloads at once. Returns a job id immediately; the aggregate
+/// success/failure report arrives as a `job://done` event once every item
+/// has been attempted.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn download_playlist(
+    app: tauri::AppHandle,
+    url: String,
+    parallel: Option<usize>,
+    limit: Option<usize>,
+    audio_only: Option<bool>,
+    quality: Option<String>,
+    format: Option<String>,
+    resolution: Option<String>,
+    include_audio: Option<bool>,
+    fps: Option<i32>,
+    container: Option<String>,
+    download_path: Option<String>,
+) -> Result<JobId, String> {
+    use tauri::Emitter;
+
+    let registry = app.state::<crate::jobs::JobRegistry>();
+    let (job_id, _token) = registry.register();
+    let job_id_for_task = job_id.clone();
+    let app_for_task = app.clone();
+
+    tauri::async_runtime::spawn(async move {
+        let result = download_playlist_async(
+            app_for_task.clone(),
+            job_id_for_task.clone(),
+            url,
+            parallel.unwrap_or(8),
+            limit,
+            audio_only.unwrap_or(false),
+            quality,
+            format,
+            resolution,
+            include_audio,
+            fps,
+            container,
+            download_path,
+        )
+        .await;
+
+        match result {
+            Ok(report) => {
+                let _ = app_for_task.emit(
+                    "job://done",
+                    json!({"id": job_id_for_task, "payload": report}),
+                );
+            }
+            Err(error) => {
+                let _ = app_for_task.emit(
+                    "job://error",
+                    json!({"id": job_id_for_task, "error": error}),
+                );
+            }
+        }
+
+        app_for_task
+            .state::<crate::jobs::JobRegistry>()
+            .remove(&job_id_for_task);
+    });
+
+    Ok(job_id)
 }
 
 #[tauri::command]
-pub async fn get_default_download_dir() -> Result<String, String> {
-    tauri::async_runtime::spawn_blocking(|| {
-        let dir = mediaflow_download_dir()?;
+pub async fn get_default_download_dir(app: tauri::AppHandle) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let dir = mediaflow_download_dir(&app)?;
         to_path_string(&dir)
     })
     .await
@@ -665,89 +1722,174 @@ pub async fn create_output_folders(base_path: String) -> Result<(), String> {
 }
 
 #[tauri::command(rename_all = "snake_case")]
-pub async fn detect_tempo(audio_path: String) -> Result<String, String> {
-    tauri::async_runtime::spawn_blocking(move || detect_tempo_sync(audio_path))
+pub async fn detect_tempo(app: tauri::AppHandle, audio_path: String) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || detect_tempo_sync(&app, audio_path))
         .await
         .map_err(|err| err.to_string())?
 }
 
 #[tauri::command(rename_all = "snake_case")]
-pub async fn detect_key(audio_path: String) -> Result<String, String> {
-    tauri::async_runtime::spawn_blocking(move || detect_key_sync(audio_path))
+pub async fn detect_key(app: tauri::AppHandle, audio_path: String) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || detect_key_sync(&app, audio_path))
         .await
         .map_err(|err| err.to_string())?
 }
 
 #[tauri::command(rename_all = "snake_case")]
 pub async fn pitch_shift(
+    app: tauri::AppHandle,
     input_path: String,
     output_path: Option<String>,
     semitones: f32,
-) -> Result<String, String> {
-    tauri::async_runtime::spawn_blocking(move || {
-        pitch_shift_sync(input_path, output_path, semitones)
-    })
-    .await
-    .map_err(|err| err.to_string())?
+) -> Result<JobId, String> {
+    let worker_app = app.clone();
+    Ok(spawn_tracked_job(app, move |job_id| {
+        pitch_shift_sync(&worker_app, &job_id, input_path, output_path, semitones)
+    }))
 }
 
 #[tauri::command(rename_all = "snake_case")]
 pub async fn separate_stems(
+    app: tauri::AppHandle,
     input_path: String,
     output_dir: Option<String>,
     stems: u8,
     format: Option<String>,
     model_name: Option<String>,
     use_gpu: Option<bool>,
-) -> Result<String, String> {
-    tauri::async_runtime::spawn_blocking(move || {
-        separate_stems_sync(input_path, output_dir, stems, format, model_name, use_gpu)
-    })
-    .await
-    .map_err(|err| err.to_string())?
+) -> Result<JobId, String> {
+    let worker_app = app.clone();
+    Ok(spawn_tracked_job(app, move |job_id| {
+        separate_stems_sync(
+            &worker_app,
+            &job_id,
+            input_path,
+            output_dir,
+            stems,
+            format,
+            model_name,
+            use_gpu,
+        )
+    }))
 }
 
 #[tauri::command]
 pub async fn upload_file(
+    app: tauri::AppHandle,
     file_name: String,
     file_data: Vec<u8>,
 ) -> Result<String, String> {
     tauri::async_runtime::spawn_blocking(move || {
-        // Create temp directory in MediaFlow folder
-        let mut temp_dir = mediaflow_download_dir()?;
-        temp_dir.push("temp");
-        fs::create_dir_all(&temp_dir)
-            .map_err(|err| format!("Failed to create temp directory: {}", err))?;
-        
+        // Uploaded files are scratch input for processing, so they live in
+        // the app cache directory rather than the user's Downloads folder.
+        let cache_dir = mediaflow_cache_dir(&app)?;
+
         // Generate unique filename
         let timestamp = timestamp_suffix();
-        let file_path = temp_dir.join(format!("{}_{}", timestamp, file_name));
-        
+        let file_path = cache_dir.join(format!("{}_{}", timestamp, file_name));
+
         // Write file
         fs::write(&file_path, file_data)
             .map_err(|err| format!("Failed to write file: {}", err))?;
-        
+
         to_path_string(&file_path)
     })
     .await
     .map_err(|err| err.to_string())?
 }
 
+/// Run a `<program> <version_arg>` probe with the console window suppressed
+/// on Windows, for the quick availability checks in `check_dependencies`.
+fn version_probe(program: &str, version_arg: &str) -> std::io::Result<Output> {
+    let mut cmd = Command::new(program);
+    cmd.arg(version_arg);
+    suppress_console(&mut cmd);
+    cmd.output()
+}
+
+/// Pull the first dotted run of digits out of a tool's raw `--version`
+/// output, e.g. `"yt-dlp 2024.12.06"` -> `"2024.12.06"`, `"Python 3.11.9"` ->
+/// `"3.11.9"`, `"ffmpeg version n6.1.1-static"` -> `"6.1.1"`. Returns `None`
+/// if no digit is present at all.
+fn extract_version(raw: &str) -> Option<String> {
+    let start = raw.find(|c: char| c.is_ascii_digit())?;
+    let rest = &raw[start..];
+    let end = rest
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(rest.len());
+    let token = rest[..end].trim_end_matches('.');
+    if token.is_empty() {
+        None
+    } else {
+        Some(token.to_string())
+    }
+}
+
+/// Component-wise `version >= minimum` comparison on dot-separated numeric
+/// strings. Missing trailing components on either side are treated as zero,
+/// so `"6.1" >= "6.1.0"` and `"2024.12" >= "2024.12.06"` compare sanely
+/// without needing a semver crate.
+fn version_meets_minimum(version: &str, minimum: &str) -> bool {
+    let parse = |s: &str| -> Vec<u64> { s.split('.').map(|part| part.parse().unwrap_or(0)).collect() };
+    let v = parse(version);
+    let m = parse(minimum);
+    for i in 0..v.len().max(m.len()) {
+        let vi = v.get(i).copied().unwrap_or(0);
+        let mi = m.get(i).copied().unwrap_or(0);
+        if vi != mi {
+            return vi > mi;
+        }
+    }
+    true
+}
+
+/// Oldest version of each dependency MediaFlow can reliably drive, so
+/// `check_dependencies` can distinguish "missing" from "present but too old
+/// to trust" (e.g. a yt-dlp build that predates a breaking YouTube change).
+fn minimum_version(tool: &str) -> &'static str {
+    match tool {
+        "yt_dlp" => "2024.08.06",
+        "python" => "3.9.0",
+        "ffmpeg" => "5.0",
+        "demucs" => "4.0.0",
+        _ => "0",
+    }
+}
+
+/// Parse `raw` for `tool` and record `parsed_version`/`required`/
+/// `meets_minimum` on `results[tool]` alongside the existing
+/// `available`/`version` fields.
+fn record_version_check(results: &mut serde_json::Value, tool: &str, raw: &str) {
+    let required = minimum_version(tool);
+    results[tool]["required"] = serde_json::json!(required);
+    match extract_version(raw) {
+        Some(parsed) => {
+            results[tool]["meets_minimum"] = serde_json::json!(version_meets_minimum(&parsed, required));
+            results[tool]["parsed_version"] = serde_json::json!(parsed);
+        }
+        None => {
+            results[tool]["meets_minimum"] = serde_json::json!(false);
+        }
+    }
+}
+
 #[tauri::command]
-pub async fn check_dependencies() -> Result<String, String> {
-    tauri::async_runtime::spawn_blocking(|| {
+pub async fn check_dependencies(app: tauri::AppHandle) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
         let mut results = serde_json::json!({
-            "yt_dlp": {"available": false, "version": serde_json::Value::Null, "error": serde_json::Value::Null},
-            "python": {"available": false, "version": serde_json::Value::Null, "error": serde_json::Value::Null},
-            "ffmpeg": {"available": false, "version": serde_json::Value::Null, "error": serde_json::Value::Null},
-            "demucs": {"available": false, "version": serde_json::Value::Null, "error": serde_json::Value::Null}
+            "yt_dlp": {"available": false, "version": serde_json::Value::Null, "error": serde_json::Value::Null, "parsed_version": serde_json::Value::Null, "required": minimum_version("yt_dlp"), "meets_minimum": false},
+            "python": {"available": false, "version": serde_json::Value::Null, "error": serde_json::Value::Null, "parsed_version": serde_json::Value::Null, "required": minimum_version("python"), "meets_minimum": false},
+            "ffmpeg": {"available": false, "version": serde_json::Value::Null, "error": serde_json::Value::Null, "parsed_version": serde_json::Value::Null, "required": minimum_version("ffmpeg"), "meets_minimum": false},
+            "demucs": {"available": false, "version": serde_json::Value::Null, "error": serde_json::Value::Null, "parsed_version": serde_json::Value::Null, "required": minimum_version("demucs"), "meets_minimum": false},
+            "cookie_browsers": detect_cookie_browsers(&app),
         });
 
         // Check yt-dlp
-        match Command::new("yt-dlp").arg("--version").output() {
+        match version_probe("yt-dlp", "--version") {
             Ok(output) if output.status.success() => {
                 let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
                 results["yt_dlp"]["available"] = serde_json::json!(true);
+                record_version_check(&mut results, "yt_dlp", &version);
                 results["yt_dlp"]["version"] = serde_json::json!(version);
             }
             Ok(_) => {
@@ -759,13 +1901,14 @@ pub async fn check_dependencies() -> Result<String, String> {
         }
 
         // Check Python (try both "python" and "py" for Windows compatibility)
-        let python_result = Command::new("python").arg("--version").output();
-        let py_result = Command::new("py").arg("--version").output();
-        
+        let python_result = version_probe("python", "--version");
+        let py_result = version_probe("py", "--version");
+
         match python_result {
             Ok(output) if output.status.success() => {
                 let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
                 results["python"]["available"] = serde_json::json!(true);
+                record_version_check(&mut results, "python", &version);
                 results["python"]["version"] = serde_json::json!(version);
             }
             _ => {
@@ -774,6 +1917,7 @@ pub async fn check_dependencies() -> Result<String, String> {
                     Ok(output) if output.status.success() => {
                         let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
                         results["python"]["available"] = serde_json::json!(true);
+                        record_version_check(&mut results, "python", &version);
                         results["python"]["version"] = serde_json::json!(version);
                     }
                     Ok(_) => {
@@ -787,7 +1931,7 @@ pub async fn check_dependencies() -> Result<String, String> {
         }
 
         // Check FFmpeg
-        match Command::new("ffmpeg").arg("-version").output() {
+        match version_probe("ffmpeg", "-version") {
             Ok(output) if output.status.success() => {
                 let version_line = String::from_utf8_lossy(&output.stdout)
                     .lines()
@@ -795,6 +1939,7 @@ pub async fn check_dependencies() -> Result<String, String> {
                     .unwrap_or("unknown")
                     .to_string();
                 results["ffmpeg"]["available"] = serde_json::json!(true);
+                record_version_check(&mut results, "ffmpeg", &version_line);
                 results["ffmpeg"]["version"] = serde_json::json!(version_line);
             }
             Ok(_) => {
@@ -806,10 +1951,11 @@ pub async fn check_dependencies() -> Result<String, String> {
         }
 
         // Check Demucs
-        match Command::new("demucs").arg("--version").output() {
+        match version_probe("demucs", "--version") {
             Ok(output) if output.status.success() => {
                 let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
                 results["demucs"]["available"] = serde_json::json!(true);
+                record_version_check(&mut results, "demucs", &version);
                 results["demucs"]["version"] = serde_json::json!(version);
             }
             Ok(_) => {
@@ -827,52 +1973,74 @@ pub async fn check_dependencies() -> Result<String, String> {
 }
 
 
-#[tauri::command]
-pub async fn download_ai_models() -> Result<String, String> {
-    tauri::async_runtime::spawn_blocking(|| {
-        let scripts_dir = find_scripts_dir()?;
-        let script_path = scripts_dir.join("download_models.py");
-        
-        // Use Python 3.11
-        let python_cmd = get_python311_command();
-        let mut cmd = Command::new(&python_cmd[0]);
-        
-        if python_cmd.len() > 1 {
-            cmd.arg(&python_cmd[1]);
-        }
-        
-        let output = cmd
-            .arg(script_path.to_str().ok_or("Invalid script path")?)
-            .output()
-            .map_err(|err| format!("Failed to run download script: {}", err))?;
-        
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        
-        if output.status.success() {
-            Ok(stdout)
-        } else {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            Err(format!("Download failed: {}", stderr))
+fn download_ai_models_sync(app: &tauri::AppHandle, job_id: &str) -> Result<String, String> {
+    let models_dir = mediaflow_models_dir(app)?;
+    let scripts_dir = find_scripts_dir()?;
+    let script_path = scripts_dir.join("download_models.py");
+
+    // Use Python 3.11
+    let python_cmd = get_python311_command();
+    let mut cmd = Command::new(&python_cmd[0]);
+
+    if python_cmd.len() > 1 {
+        cmd.arg(&python_cmd[1]);
+    }
+
+    cmd.arg(script_path.to_str().ok_or("Invalid script path")?)
+        .arg(to_path_string(&models_dir)?);
+    let output = run_tracked(app, job_id, cmd)?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+
+    if output.status.success() {
+        Ok(stdout)
+    } else {
+        // Killed mid-download via `cancel_job`: the models directory holds
+        // other already-installed models too, so don't wipe it wholesale.
+        // Instead reuse the checksum-verified registry scan to find exactly
+        // the half-downloaded file(s) - wrong size or hash - and remove
+        // just those.
+        if app.state::<crate::jobs::JobRegistry>().is_cancelled(job_id) {
+            for model in crate::models::scan(&models_dir) {
+                if model.state == crate::models::ModelState::Corrupt {
+                    let _ = fs::remove_file(models_dir.join(model.filename));
+                }
+            }
         }
-    })
-    .await
-    .map_err(|err| err.to_string())?
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(format!("Download failed: {}", stderr))
+    }
 }
 
 #[tauri::command]
-pub async fn check_models_installed() -> Result<String, String> {
-    tauri::async_runtime::spawn_blocking(|| {
-        // Check if model file exists in default location
-        let model_dir = std::path::PathBuf::from("/tmp/audio-separator-models");
-        let model_file = model_dir.join("UVR-MDX-NET-Inst_HQ_3.onnx");
-        
-        let installed = model_file.exists();
-        
-        serde_json::to_string(&serde_json::json!({
-            "installed": installed,
-            "model_path": model_file.to_string_lossy()
-        }))
-        .map_err(|e| e.to_string())
+pub async fn download_ai_models(app: tauri::AppHandle) -> Result<JobId, String> {
+    let worker_app = app.clone();
+    Ok(spawn_tracked_job(app, move |job_id| {
+        download_ai_models_sync(&worker_app, &job_id)
+    }))
+}
+
+/// Install every artifact (AI model or bundled tool) listed in the signed
+/// manifest at `manifest_url`, replacing the old unverified
+/// `download_models.py` shell-out for anything the manifest covers. See
+/// [`crate::updater::apply_manifest`] for the signature verification this
+/// goes through before anything is written to disk.
+#[tauri::command]
+pub async fn update_signed_assets(app: tauri::AppHandle, manifest_url: String) -> Result<String, String> {
+    let installed = crate::updater::apply_manifest(&app, &manifest_url).await?;
+    serde_json::to_string(&serde_json::json!({ "installed": installed })).map_err(|err| err.to_string())
+}
+
+/// Scan the app's model directory against [`crate::models::MODEL_REGISTRY`],
+/// reporting each model's installed/corrupt/missing state by comparing file
+/// size and SHA-256 against the expected values rather than trusting mere
+/// file existence.
+#[tauri::command]
+pub async fn check_models_installed(app: tauri::AppHandle) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let model_dir = mediaflow_models_dir(&app)?;
+        let models = crate::models::scan(&model_dir);
+        serde_json::to_string(&serde_json::json!({ "models": models })).map_err(|e| e.to_string())
     })
     .await
     .map_err(|err| err.to_string())?
@@ -914,43 +2082,252 @@ fn format_bytes(bytes: u64) -> String {
 }
 
 #[tauri::command]
-pub async fn get_cache_size() -> Result<String, String> {
-    tauri::async_runtime::spawn_blocking(|| {
-        let mut total_size: u64 = 0;
-        
-        // Only count MediaFlow temp folder (uploaded files, processing temp)
-        // NOT counting AI models - those are required files, not cache
-        if let Ok(mediaflow_dir) = mediaflow_download_dir() {
-            let temp_dir = mediaflow_dir.join("temp");
-            if temp_dir.exists() {
-                total_size += get_dir_size(&temp_dir);
-            }
-        }
-        
-        Ok(format_bytes(total_size))
+pub async fn get_cache_size(app: tauri::AppHandle) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        // Only the app's cache directory counts (uploaded files, processing
+        // scratch output) - AI models live in the app data directory and
+        // are required files, not cache.
+        let cache_dir = mediaflow_cache_dir(&app)?;
+        let (logical, on_disk) = crate::compact_cache::sizes(&cache_dir);
+        serde_json::to_string(&serde_json::json!({
+            "logical_size": format_bytes(logical),
+            "on_disk_size": format_bytes(on_disk),
+        }))
+        .map_err(|err| err.to_string())
     })
     .await
     .map_err(|err| err.to_string())?
 }
 
+/// Compress eligible idle scratch files (logs, JSON sidecars, WAV
+/// intermediates) in the app cache directory with a streaming zstd codec,
+/// see [`crate::compact_cache::compact`]. Optional maintenance step users
+/// can run instead of a full `clear_cache`.
 #[tauri::command]
-pub async fn clear_cache() -> Result<String, String> {
-    tauri::async_runtime::spawn_blocking(|| {
-        let mut cleared = 0u64;
-        
-        // Clear MediaFlow temp folder only
-        // NOT clearing AI models
-        if let Ok(mediaflow_dir) = mediaflow_download_dir() {
-            let temp_dir = mediaflow_dir.join("temp");
-            if temp_dir.exists() {
-                cleared += get_dir_size(&temp_dir);
-                let _ = fs::remove_dir_all(&temp_dir);
-                let _ = fs::create_dir_all(&temp_dir); // Recreate empty
-            }
-        }
-        
+pub async fn compact_cache(app: tauri::AppHandle) -> Result<String, String> {
+    let cache_dir = mediaflow_cache_dir(&app)?;
+    let referenced = app.state::<crate::jobs::JobRegistry>().referenced_paths();
+    let report = crate::compact_cache::compact(&cache_dir, &referenced).await?;
+    serde_json::to_string(&report).map_err(|err| err.to_string())
+}
+
+/// Walk the cache and download directories in parallel (rayon), reporting
+/// total size plus duplicate-file groups (staged size -> prefix-hash ->
+/// full-hash detection, see [`crate::cache_analysis::analyze`]) and the
+/// bytes that could be freed by keeping one copy of each. Informs
+/// `clear_cache` instead of it blindly wiping everything.
+#[tauri::command]
+pub async fn analyze_cache(app: tauri::AppHandle) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let dirs = vec![mediaflow_cache_dir(&app)?, mediaflow_download_dir(&app)?];
+        let report = crate::cache_analysis::analyze(&dirs);
+        serde_json::to_string(&report).map_err(|err| err.to_string())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+#[tauri::command]
+pub async fn clear_cache(app: tauri::AppHandle) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let cache_dir = mediaflow_cache_dir(&app)?;
+        let cleared = get_dir_size(&cache_dir);
+        let _ = fs::remove_dir_all(&cache_dir);
+        fs::create_dir_all(&cache_dir)
+            .map_err(|err| format!("Failed to recreate app cache directory: {err}"))?;
+
         Ok(format!("Cleared {}", format_bytes(cleared)))
     })
     .await
     .map_err(|err| err.to_string())?
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_version_pulls_first_dotted_digit_run() {
+        assert_eq!(extract_version("yt-dlp 2024.12.06"), Some("2024.12.06".to_string()));
+        assert_eq!(extract_version("Python 3.11.9"), Some("3.11.9".to_string()));
+        assert_eq!(extract_version("ffmpeg version n6.1.1-static"), Some("6.1.1".to_string()));
+    }
+
+    #[test]
+    fn extract_version_returns_none_without_digits() {
+        assert_eq!(extract_version("command not found"), None);
+    }
+
+    #[test]
+    fn extract_version_trims_trailing_dot() {
+        assert_eq!(extract_version("demucs version 4.0."), Some("4.0".to_string()));
+    }
+
+    #[test]
+    fn version_meets_minimum_equal_is_sufficient() {
+        assert!(version_meets_minimum("6.1.0", "6.1.0"));
+    }
+
+    #[test]
+    fn version_meets_minimum_missing_trailing_components_are_zero() {
+        assert!(version_meets_minimum("6.1", "6.1.0"));
+        assert!(!version_meets_minimum("2024.12", "2024.12.06"));
+    }
+
+    #[test]
+    fn version_meets_minimum_rejects_older_version() {
+        assert!(!version_meets_minimum("5.9.0", "6.1.0"));
+    }
+
+    #[test]
+    fn version_meets_minimum_accepts_newer_version() {
+        assert!(version_meets_minimum("6.2.0", "6.1.0"));
+    }
+
+    #[test]
+    fn parse_progress_line_computes_percent() {
+        let progress = parse_progress_line("512/1024/102400/5").unwrap();
+        assert_eq!(progress.downloaded, 512);
+        assert_eq!(progress.total, 1024);
+        assert_eq!(progress.speed, 102400.0);
+        assert_eq!(progress.eta, 5);
+        assert_eq!(progress.percent, 50.0);
+    }
+
+    #[test]
+    fn parse_progress_line_treats_na_fields_as_unknown() {
+        let progress = parse_progress_line("NA/NA/NA/NA").unwrap();
+        assert_eq!(progress.downloaded, 0);
+        assert_eq!(progress.total, 0);
+        assert_eq!(progress.speed, 0.0);
+        assert_eq!(progress.eta, -1);
+        assert_eq!(progress.percent, 0.0);
+    }
+
+    #[test]
+    fn parse_progress_line_rejects_malformed_line() {
+        assert!(parse_progress_line("not-progress-output").is_none());
+    }
+
+    #[test]
+    fn build_video_format_selector_tries_preferred_codecs_first() {
+        let selector = build_video_format_selector(
+            "1080",
+            60,
+            true,
+            &["avc1".to_string(), "vp9".to_string()],
+            &[],
+        );
+        let candidates: Vec<&str> = selector.split('/').collect();
+        assert_eq!(candidates.len(), 3);
+        assert!(candidates[0].contains("[vcodec^=avc1]"));
+        assert!(candidates[1].contains("[vcodec^=vp9]"));
+        assert!(!candidates[2].contains("vcodec^="));
+        assert!(candidates[0].contains("bestaudio"));
+    }
+
+    #[test]
+    fn build_video_format_selector_applies_exclude_clause() {
+        let selector = build_video_format_selector("720", 30, false, &[], &["av01".to_string()]);
+        assert!(selector.contains("[vcodec!^=av01]"));
+        assert!(!selector.contains("bestaudio"));
+    }
+
+    #[test]
+    fn build_video_format_selector_without_audio_adds_no_best_fallback() {
+        let selector = build_video_format_selector("480", 30, false, &[], &[]);
+        assert!(!selector.contains("best[height"));
+    }
+
+    #[test]
+    fn build_metadata_args_includes_embed_flags() {
+        let (args, subtitle_paths) = build_metadata_args(
+            true,
+            true,
+            true,
+            &None,
+            &None,
+            std::path::Path::new("/tmp/mediaflow"),
+            "video",
+        );
+        assert!(args.contains(&"--embed-metadata".to_string()));
+        assert!(args.contains(&"--embed-thumbnail".to_string()));
+        assert!(args.contains(&"--embed-chapters".to_string()));
+        assert!(subtitle_paths.is_empty());
+    }
+
+    #[test]
+    fn build_metadata_args_builds_subtitle_paths_per_lang() {
+        let subtitles = Some(SubtitleOpts {
+            auto: false,
+            langs: vec!["en".to_string(), "es".to_string()],
+        });
+        let (args, subtitle_paths) = build_metadata_args(
+            false,
+            false,
+            false,
+            &subtitles,
+            &None,
+            std::path::Path::new("/tmp/mediaflow"),
+            "video",
+        );
+        assert!(args.contains(&"--write-subs".to_string()));
+        assert_eq!(
+            subtitle_paths,
+            vec![
+                "/tmp/mediaflow/video.en.vtt".to_string(),
+                "/tmp/mediaflow/video.es.vtt".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn build_metadata_args_converts_subs_and_uses_target_ext() {
+        let subtitles = Some(SubtitleOpts {
+            auto: true,
+            langs: vec!["en".to_string()],
+        });
+        let (args, subtitle_paths) = build_metadata_args(
+            false,
+            false,
+            false,
+            &subtitles,
+            &Some("srt".to_string()),
+            std::path::Path::new("/tmp/mediaflow"),
+            "video",
+        );
+        assert!(args.contains(&"--write-auto-subs".to_string()));
+        assert!(args.contains(&"--convert-subs".to_string()));
+        assert_eq!(subtitle_paths, vec!["/tmp/mediaflow/video.en.srt".to_string()]);
+    }
+
+    #[test]
+    fn build_auth_args_combines_cookies_and_extractor_args() {
+        let args = build_auth_args(
+            &Some("firefox".to_string()),
+            &Some("/tmp/cookies.txt".to_string()),
+            &Some(vec!["android".to_string(), "web".to_string()]),
+            &Some("token123".to_string()),
+        );
+        assert!(args.contains(&"--cookies-from-browser".to_string()));
+        assert!(args.contains(&"firefox".to_string()));
+        assert!(args.contains(&"--cookies".to_string()));
+        assert!(args.contains(&"/tmp/cookies.txt".to_string()));
+        let extractor_idx = args.iter().position(|a| a == "--extractor-args").unwrap();
+        assert_eq!(
+            args[extractor_idx + 1],
+            "youtube:player_client=android,web;po_token=token123"
+        );
+    }
+
+    #[test]
+    fn build_auth_args_empty_when_nothing_set() {
+        assert!(build_auth_args(&None, &None, &None, &None).is_empty());
+    }
+
+    #[test]
+    fn build_auth_args_skips_extractor_args_when_clients_list_is_empty() {
+        let args = build_auth_args(&None, &None, &Some(vec![]), &None);
+        assert!(args.is_empty());
+    }
+}
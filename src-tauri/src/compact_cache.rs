@@ -0,0 +1,193 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use async_compression::tokio::bufread::ZstdDecoder;
+use async_compression::tokio::write::ZstdEncoder;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader};
+
+const COMPRESSED_EXT: &str = "zst";
+
+/// Scratch-file kinds eligible for transparent compression: logs, JSON
+/// sidecars, subtitles, and WAV intermediates produced mid-pipeline. WAV
+/// accounts for most of the reclaimable space, but it's also the one format
+/// `detect_tempo`/`detect_key`/`pitch_shift`/`separate_stems` read straight
+/// off disk by literal path (they shell out to ffmpeg/Python, not through
+/// [`read_through`]) — so [`compact`] skips whatever [`JobRegistry`]
+/// currently reports as referenced instead of disqualifying the extension
+/// outright. Final media containers (mp4/webm/mp3/etc.) are already
+/// compressed and aren't worth the CPU.
+///
+/// [`JobRegistry`]: crate::jobs::JobRegistry
+fn is_compressible(path: &Path) -> bool {
+    matches!(
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase())
+            .as_deref(),
+        Some("log") | Some("json") | Some("wav") | Some("srt") | Some("vtt")
+    )
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CompactReport {
+    pub files_compressed: usize,
+    pub logical_bytes: u64,
+    pub on_disk_bytes: u64,
+}
+
+/// Sidecar tracking each compressed file's original (logical) size, since a
+/// zstd frame's on-disk size alone can't tell `get_cache_size` how much
+/// space was reclaimed without decompressing every file on every check.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SizeIndex(HashMap<String, u64>);
+
+fn size_index_path(cache_dir: &Path) -> PathBuf {
+    cache_dir.join(".compact-cache-sizes.json")
+}
+
+fn load_size_index(cache_dir: &Path) -> SizeIndex {
+    std::fs::read_to_string(size_index_path(cache_dir))
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn save_size_index(cache_dir: &Path, index: &SizeIndex) -> Result<(), String> {
+    let text = serde_json::to_string(index).map_err(|err| err.to_string())?;
+    std::fs::write(size_index_path(cache_dir), text).map_err(|err| err.to_string())
+}
+
+fn walk(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            walk(&path, out);
+        } else {
+            out.push(path);
+        }
+    }
+}
+
+/// Compress every eligible idle scratch file under `cache_dir` with a
+/// streaming zstd codec, replacing it with a `.zst` sibling and recording
+/// its original size in the size index so [`sizes`] can report logical vs.
+/// on-disk totals. Already-compressed files are left alone, as is anything
+/// in `referenced` — paths a [`crate::jobs::JobRegistry`] snapshot reports as
+/// still open by an in-flight job.
+pub async fn compact(cache_dir: &Path, referenced: &HashSet<PathBuf>) -> Result<CompactReport, String> {
+    let mut files = Vec::new();
+    walk(cache_dir, &mut files);
+
+    let mut index = load_size_index(cache_dir);
+    let mut report = CompactReport::default();
+
+    for path in files {
+        if path.extension().and_then(|ext| ext.to_str()) == Some(COMPRESSED_EXT)
+            || !is_compressible(&path)
+            || referenced.contains(&path)
+        {
+            continue;
+        }
+
+        let Ok(metadata) = std::fs::metadata(&path) else {
+            continue;
+        };
+        let logical_size = metadata.len();
+
+        let mut dest = path.clone().into_os_string();
+        dest.push(".");
+        dest.push(COMPRESSED_EXT);
+        let dest = PathBuf::from(dest);
+
+        let input = tokio::fs::read(&path).await.map_err(|err| err.to_string())?;
+        let output = tokio::fs::File::create(&dest).await.map_err(|err| err.to_string())?;
+        let mut encoder = ZstdEncoder::new(output);
+        encoder.write_all(&input).await.map_err(|err| err.to_string())?;
+        encoder.shutdown().await.map_err(|err| err.to_string())?;
+
+        let on_disk_size = tokio::fs::metadata(&dest)
+            .await
+            .map_err(|err| err.to_string())?
+            .len();
+        tokio::fs::remove_file(&path).await.map_err(|err| err.to_string())?;
+
+        index.0.insert(dest.to_string_lossy().to_string(), logical_size);
+        report.files_compressed += 1;
+        report.logical_bytes += logical_size;
+        report.on_disk_bytes += on_disk_size;
+    }
+
+    save_size_index(cache_dir, &index)?;
+    Ok(report)
+}
+
+/// Read `path` back to its original bytes, transparently decompressing if it
+/// was stored as a `.zst` sidecar by [`compact`].
+pub async fn read_through(path: &Path) -> std::io::Result<Vec<u8>> {
+    if path.extension().and_then(|ext| ext.to_str()) == Some(COMPRESSED_EXT) {
+        let file = tokio::fs::File::open(path).await?;
+        let mut decoder = ZstdDecoder::new(BufReader::new(file));
+        let mut buf = Vec::new();
+        decoder.read_to_end(&mut buf).await?;
+        Ok(buf)
+    } else {
+        tokio::fs::read(path).await
+    }
+}
+
+/// Total logical (decompressed) and on-disk bytes under `cache_dir`,
+/// combining the size index from [`compact`] with plain file sizes for
+/// anything left uncompressed. Returns `(logical, on_disk)`.
+pub fn sizes(cache_dir: &Path) -> (u64, u64) {
+    let index = load_size_index(cache_dir);
+    let mut files = Vec::new();
+    walk(cache_dir, &mut files);
+
+    let mut logical = 0u64;
+    let mut on_disk = 0u64;
+
+    for path in files {
+        let Ok(metadata) = std::fs::metadata(&path) else {
+            continue;
+        };
+        let disk_len = metadata.len();
+        on_disk += disk_len;
+        logical += index
+            .0
+            .get(&path.to_string_lossy().to_string())
+            .copied()
+            .unwrap_or(disk_len);
+    }
+
+    (logical, on_disk)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_compressible_accepts_known_scratch_extensions() {
+        assert!(is_compressible(Path::new("job.log")));
+        assert!(is_compressible(Path::new("manifest.json")));
+        assert!(is_compressible(Path::new("take.wav")));
+        assert!(is_compressible(Path::new("english.srt")));
+        assert!(is_compressible(Path::new("english.vtt")));
+    }
+
+    #[test]
+    fn is_compressible_is_case_insensitive() {
+        assert!(is_compressible(Path::new("TAKE.WAV")));
+    }
+
+    #[test]
+    fn is_compressible_rejects_final_media_containers() {
+        assert!(!is_compressible(Path::new("video.mp4")));
+        assert!(!is_compressible(Path::new("audio.mp3")));
+        assert!(!is_compressible(Path::new("no_extension")));
+    }
+}
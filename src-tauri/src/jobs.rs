@@ -0,0 +1,211 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Child;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+pub type JobId = String;
+
+/// Cooperative cancellation signal shared between a command's spawned task
+/// and the `cancel_job` handler. Checked between steps of long-running work;
+/// killing the underlying subprocess outright is [`JobRegistry`]'s job.
+#[derive(Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Tracks cancellation tokens and the killable child process(es) for every
+/// in-flight job, keyed by the job id returned synchronously to the
+/// frontend when the job is started. A job id maps to *multiple* children
+/// when it fans out into concurrent subprocesses (e.g. a playlist download
+/// running several `yt-dlp` instances under one job id via
+/// `buffer_unordered`) — tracking a single child per job would let one item
+/// finishing early evict a still-running sibling's entry.
+#[derive(Default)]
+pub struct JobRegistry {
+    tokens: Mutex<HashMap<JobId, CancellationToken>>,
+    children: Mutex<HashMap<JobId, Vec<Arc<Mutex<Child>>>>>,
+    referenced_paths: Mutex<HashMap<PathBuf, u32>>,
+}
+
+/// RAII guard returned by [`JobRegistry::reference_path`]: keeps a path
+/// marked as "in use by a job" for as long as the guard is alive, and
+/// releases it on drop regardless of which early return or panic unwinds
+/// past it. `compact_cache` consults [`JobRegistry::is_path_referenced`]
+/// before compressing a file so it never compacts one a job still has open
+/// by path.
+pub struct PathGuard<'a> {
+    registry: &'a JobRegistry,
+    path: PathBuf,
+}
+
+impl Drop for PathGuard<'_> {
+    fn drop(&mut self) {
+        self.registry.release_path(&self.path);
+    }
+}
+
+impl JobRegistry {
+    /// Allocate a new job id and register its cancellation token.
+    pub fn register(&self) -> (JobId, CancellationToken) {
+        let id = uuid::Uuid::new_v4().to_string();
+        let token = CancellationToken::default();
+        self.tokens.lock().unwrap().insert(id.clone(), token.clone());
+        (id, token)
+    }
+
+    /// Signal cancellation for `job_id` and kill every tracked child process
+    /// currently running under it (there may be several, for a fanned-out
+    /// job like a parallel playlist download). Returns `false` if the job is
+    /// unknown (already finished or never existed).
+    pub fn cancel(&self, job_id: &str) -> bool {
+        let known = match self.tokens.lock().unwrap().get(job_id) {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        };
+
+        if let Some(children) = self.children.lock().unwrap().remove(job_id) {
+            for child in children {
+                let _ = child.lock().unwrap().kill();
+            }
+        }
+
+        known
+    }
+
+    /// Drop the bookkeeping for a finished job.
+    pub fn remove(&self, job_id: &str) {
+        self.tokens.lock().unwrap().remove(job_id);
+        self.children.lock().unwrap().remove(job_id);
+    }
+
+    /// Check whether `job_id` has been cancelled, used by long-running
+    /// commands to decide whether to clean up partial output after their
+    /// subprocess was killed mid-run.
+    pub fn is_cancelled(&self, job_id: &str) -> bool {
+        self.tokens
+            .lock()
+            .unwrap()
+            .get(job_id)
+            .map(|token| token.is_cancelled())
+            .unwrap_or(false)
+    }
+
+    /// Track `child` as one of the killable subprocesses for `job_id`,
+    /// alongside any others already running under the same job id.
+    pub fn track_child(&self, job_id: &str, child: Arc<Mutex<Child>>) {
+        self.children
+            .lock()
+            .unwrap()
+            .entry(job_id.to_string())
+            .or_default()
+            .push(child);
+    }
+
+    /// Stop tracking `child` for `job_id` once it has exited on its own, so
+    /// a finished process can't be killed by a late cancel and a still-
+    /// running sibling under the same job id isn't affected.
+    pub fn untrack_child(&self, job_id: &str, child: &Arc<Mutex<Child>>) {
+        let mut children = self.children.lock().unwrap();
+        if let Some(list) = children.get_mut(job_id) {
+            list.retain(|tracked| !Arc::ptr_eq(tracked, child));
+            if list.is_empty() {
+                children.remove(job_id);
+            }
+        }
+    }
+
+    /// Mark `path` as referenced by an in-flight job for as long as the
+    /// returned guard is alive. Reference-counted so two overlapping jobs on
+    /// the same path (e.g. `detect_tempo` and `detect_key` racing on one
+    /// upload) don't let one's completion unreference it out from under the
+    /// other.
+    pub fn reference_path(&self, path: &Path) -> PathGuard<'_> {
+        *self
+            .referenced_paths
+            .lock()
+            .unwrap()
+            .entry(path.to_path_buf())
+            .or_insert(0) += 1;
+        PathGuard {
+            registry: self,
+            path: path.to_path_buf(),
+        }
+    }
+
+    fn release_path(&self, path: &Path) {
+        let mut paths = self.referenced_paths.lock().unwrap();
+        if let Some(count) = paths.get_mut(path) {
+            *count -= 1;
+            if *count == 0 {
+                paths.remove(path);
+            }
+        }
+    }
+
+    /// Snapshot of every path currently referenced by an in-flight job, for
+    /// `compact_cache` to skip over.
+    pub fn referenced_paths(&self) -> HashSet<PathBuf> {
+        self.referenced_paths.lock().unwrap().keys().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_then_cancel_signals_the_token() {
+        let registry = JobRegistry::default();
+        let (id, token) = registry.register();
+        assert!(!token.is_cancelled());
+        assert!(registry.cancel(&id));
+        assert!(token.is_cancelled());
+        assert!(registry.is_cancelled(&id));
+    }
+
+    #[test]
+    fn cancel_unknown_job_returns_false() {
+        let registry = JobRegistry::default();
+        assert!(!registry.cancel("does-not-exist"));
+    }
+
+    #[test]
+    fn remove_drops_bookkeeping_for_a_job() {
+        let registry = JobRegistry::default();
+        let (id, _token) = registry.register();
+        registry.remove(&id);
+        assert!(!registry.is_cancelled(&id));
+        assert!(!registry.cancel(&id));
+    }
+
+    #[test]
+    fn reference_path_is_reference_counted_across_overlapping_guards() {
+        let registry = JobRegistry::default();
+        let path = PathBuf::from("/tmp/mediaflow-test-audio.wav");
+
+        let first = registry.reference_path(&path);
+        let second = registry.reference_path(&path);
+        assert!(registry.referenced_paths().contains(&path));
+
+        drop(first);
+        assert!(
+            registry.referenced_paths().contains(&path),
+            "one guard dropping shouldn't unreference a path a sibling guard still holds"
+        );
+
+        drop(second);
+        assert!(!registry.referenced_paths().contains(&path));
+    }
+}
@@ -1,4 +1,19 @@
+mod binaries;
+mod cache_analysis;
 mod commands;
+mod compact_cache;
+mod innertube;
+mod jobs;
+mod media_protocol;
+mod models;
+mod server;
+mod updater;
+
+use jobs::JobRegistry;
+use media_protocol::MediaKeyStore;
+use std::sync::Arc;
+use tauri::Manager;
+use tokio::sync::Mutex as AsyncMutex;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -6,13 +21,14 @@ pub fn run() {
     let _ = dotenvy::from_filename("../.env.local");
     let _ = dotenvy::from_filename(".env.local");
     let _ = dotenvy::dotenv();
-    
+
     tauri::Builder::default()
         .invoke_handler(tauri::generate_handler![
             commands::get_video_info,
             commands::get_playlist_info,
             commands::download_audio,
             commands::download_video,
+            commands::download_playlist,
             commands::get_default_download_dir,
             commands::select_folder,
             commands::create_output_folders,
@@ -23,10 +39,33 @@ pub fn run() {
             commands::upload_file,
             commands::check_dependencies,
             commands::download_ai_models,
+            commands::update_signed_assets,
             commands::check_models_installed,
             commands::get_cache_size,
-            commands::clear_cache
+            commands::analyze_cache,
+            commands::compact_cache,
+            commands::clear_cache,
+            commands::register_media_source,
+            commands::cancel_job,
+            commands::update_ytdlp,
+            commands::update_ffmpeg
         ])
+        .manage(Arc::new(MediaKeyStore::default()))
+        .manage(JobRegistry::default())
+        .register_uri_scheme_protocol("mediaflow", |app, request| {
+            let store = app.state::<Arc<MediaKeyStore>>().inner().clone();
+            media_protocol::handle_request(&store, &request)
+        })
+        .register_asynchronous_uri_scheme_protocol("mediaflow-api", |app, request, responder| {
+            // Bridge into the embedded axum router so `/stream/:id`,
+            // `/stems/:job/:stem`, and `/thumbnail/:id` share one HTTP
+            // surface that LAN devices can hit too, not just the webview.
+            let router = app.state::<server::SharedRouter>().inner().clone();
+            tauri::async_runtime::spawn(async move {
+                let response = server::handle(router, request).await;
+                responder.respond(response);
+            });
+        })
         .setup(|app| {
             if cfg!(debug_assertions) {
                 app.handle().plugin(
@@ -39,6 +78,12 @@ pub fn run() {
             app.handle().plugin(tauri_plugin_store::Builder::default().build())?;
             app.handle().plugin(tauri_plugin_fs::init())?;
             app.handle().plugin(tauri_plugin_shell::init())?;
+
+            let media_store = app.state::<Arc<MediaKeyStore>>().inner().clone();
+            let router: server::SharedRouter =
+                Arc::new(AsyncMutex::new(server::build_router(media_store)));
+            app.manage(router);
+
             Ok(())
         })
         .run(tauri::generate_context!())
@@ -0,0 +1,349 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tauri::http::{header, Request, Response, StatusCode};
+
+/// Opaque-key -> absolute-path table so the frontend never sees real filesystem paths.
+///
+/// Keys are handed out by [`MediaKeyStore::register`] (called from commands like
+/// `download_video`/`separate_stems` once a file is ready) and consumed by the
+/// `mediaflow://` URI scheme handler registered in `lib.rs`.
+#[derive(Default)]
+pub struct MediaKeyStore(Mutex<HashMap<String, PathBuf>>);
+
+impl MediaKeyStore {
+    /// Register `path` and return the opaque key the frontend should use as
+    /// `mediaflow://<key>`.
+    pub fn register(&self, path: PathBuf) -> String {
+        let key = uuid::Uuid::new_v4().to_string();
+        self.0.lock().unwrap().insert(key.clone(), path);
+        key
+    }
+
+    fn resolve(&self, key: &str) -> Option<PathBuf> {
+        self.0.lock().unwrap().get(key).cloned()
+    }
+
+    /// Same lookup as [`Self::resolve`], exposed for the embedded axum
+    /// router so its route handlers can share this table.
+    pub(crate) fn path_for(&self, key: &str) -> Option<PathBuf> {
+        self.resolve(key)
+    }
+}
+
+pub(crate) fn mime_for(path: &std::path::Path) -> &'static str {
+    match path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("")
+        .to_lowercase()
+        .as_str()
+    {
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "mkv" => "video/x-matroska",
+        "mov" => "video/quicktime",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "flac" => "audio/flac",
+        "m4a" => "audio/mp4",
+        "ogg" => "audio/ogg",
+        _ => "application/octet-stream",
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct ByteRange {
+    start: u64,
+    end: u64,
+}
+
+/// Parse a single-range `Range: bytes=start-end` header. Multi-range requests
+/// are not supported; we only ever need one contiguous chunk for scrubbing.
+fn parse_range(header_value: &str, len: u64) -> Option<ByteRange> {
+    let spec = header_value.strip_prefix("bytes=")?;
+    let (start_str, end_str) = spec.split_once('-')?;
+
+    let start: u64 = if start_str.is_empty() {
+        // "bytes=-500" means the last 500 bytes.
+        let suffix: u64 = end_str.parse().ok()?;
+        return Some(ByteRange {
+            start: len.saturating_sub(suffix),
+            end: len.saturating_sub(1),
+        });
+    } else {
+        start_str.parse().ok()?
+    };
+
+    let end = if end_str.is_empty() {
+        len.saturating_sub(1)
+    } else {
+        end_str.parse().ok()?
+    };
+
+    Some(ByteRange { start, end })
+}
+
+/// Largest chunk [`read_range`] will ever buffer in memory for one request.
+/// A player's first request is commonly an unranged GET or an open-ended
+/// `Range: bytes=0-`, both of which would otherwise buffer the entire file —
+/// defeating the whole point of range support for large downloads. Capping
+/// the served chunk (and reporting it as a `206 Partial Content` subset via
+/// `Content-Range` even when more than this was requested) bounds memory use
+/// regardless of file size; a compliant `<audio>`/`<video>` element just
+/// issues another Range request for the rest.
+const MAX_CHUNK_BYTES: u64 = 8 * 1024 * 1024;
+
+fn empty_response(status: StatusCode) -> Response<Cow<'static, [u8]>> {
+    Response::builder()
+        .status(status)
+        .body(Cow::Borrowed(&[] as &[u8]))
+        .unwrap()
+}
+
+/// Outcome of reading `path` against an optional `Range` header, independent
+/// of which HTTP framework ends up turning it into a response. Shared by the
+/// `mediaflow://` scheme handler below and the embedded axum router in
+/// `server.rs` so both surfaces get the same scrub-friendly range semantics
+/// instead of one of them re-implementing a naive full-body handler.
+pub(crate) enum RangeBody {
+    NotFound,
+    Full {
+        mime: &'static str,
+        bytes: Vec<u8>,
+    },
+    Partial {
+        mime: &'static str,
+        bytes: Vec<u8>,
+        start: u64,
+        end: u64,
+        total: u64,
+    },
+    Unsatisfiable {
+        total: u64,
+    },
+}
+
+/// Read `path`, honoring `range_header` (a raw `Range: bytes=...` value) the
+/// same way `handle_request` always has: no header serves the whole file
+/// when it's small enough, otherwise (and for any explicit range) at most
+/// [`MAX_CHUNK_BYTES`] is buffered and returned as a `206 Partial Content`
+/// chunk — `Content-Range` reflects exactly what was served so the caller
+/// can request the rest. An out-of-bounds range reports `416` with the
+/// total length so the caller can retry.
+pub(crate) fn read_range(path: &Path, range_header: Option<&str>) -> RangeBody {
+    let Ok(mut file) = File::open(path) else {
+        return RangeBody::NotFound;
+    };
+
+    let Ok(metadata) = file.metadata() else {
+        return RangeBody::NotFound;
+    };
+    let len = metadata.len();
+    let mime = mime_for(path);
+
+    let range = match range_header {
+        None if len <= MAX_CHUNK_BYTES => {
+            // Whole file already fits under the cap: serve it outright, no
+            // need to manufacture a range just to bound memory use.
+            let mut buf = Vec::with_capacity(len as usize);
+            if file.read_to_end(&mut buf).is_err() {
+                return RangeBody::NotFound;
+            }
+            return RangeBody::Full { mime, bytes: buf };
+        }
+        // No header at all: treat it as "from the start", same as the
+        // open-ended `bytes=0-` a lot of players send explicitly.
+        None => ByteRange { start: 0, end: len.saturating_sub(1) },
+        Some(header) => match parse_range(header, len) {
+            Some(range) => range,
+            None => return RangeBody::Unsatisfiable { total: len },
+        },
+    };
+
+    if range.start >= len || range.end >= len || range.start > range.end {
+        return RangeBody::Unsatisfiable { total: len };
+    }
+
+    let capped_end = range.end.min(range.start + MAX_CHUNK_BYTES - 1).min(len - 1);
+    let chunk_len = (capped_end - range.start + 1) as usize;
+    let mut buf = vec![0u8; chunk_len];
+    if file.seek(SeekFrom::Start(range.start)).is_err() || file.read_exact(&mut buf).is_err() {
+        return RangeBody::NotFound;
+    }
+
+    RangeBody::Partial {
+        mime,
+        bytes: buf,
+        start: range.start,
+        end: capped_end,
+        total: len,
+    }
+}
+
+/// Handle a `mediaflow://<key>` request, serving the resolved file with HTTP
+/// range support so `<audio>`/`<video>` elements can scrub large downloads
+/// without loading them in full.
+pub fn handle_request(
+    store: &MediaKeyStore,
+    request: &Request<Vec<u8>>,
+) -> Response<Cow<'static, [u8]>> {
+    let key = request.uri().host().unwrap_or_default();
+
+    let Some(path) = store.resolve(key) else {
+        return empty_response(StatusCode::NOT_FOUND);
+    };
+
+    let range_header = request
+        .headers()
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok());
+
+    match read_range(&path, range_header) {
+        RangeBody::NotFound => empty_response(StatusCode::NOT_FOUND),
+        RangeBody::Full { mime, bytes } => Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, mime)
+            .header(header::CONTENT_LENGTH, bytes.len())
+            .header(header::ACCEPT_RANGES, "bytes")
+            .body(Cow::Owned(bytes))
+            .unwrap(),
+        RangeBody::Partial {
+            mime,
+            bytes,
+            start,
+            end,
+            total,
+        } => Response::builder()
+            .status(StatusCode::PARTIAL_CONTENT)
+            .header(header::CONTENT_TYPE, mime)
+            .header(header::CONTENT_LENGTH, bytes.len())
+            .header(header::ACCEPT_RANGES, "bytes")
+            .header(header::CONTENT_RANGE, format!("bytes {start}-{end}/{total}"))
+            .body(Cow::Owned(bytes))
+            .unwrap(),
+        RangeBody::Unsatisfiable { total } => Response::builder()
+            .status(StatusCode::RANGE_NOT_SATISFIABLE)
+            .header(header::CONTENT_RANGE, format!("bytes */{total}"))
+            .body(Cow::Borrowed(&[] as &[u8]))
+            .unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_range_start_and_end() {
+        let range = parse_range("bytes=0-499", 1000).unwrap();
+        assert_eq!(range, ByteRange { start: 0, end: 499 });
+    }
+
+    #[test]
+    fn parse_range_open_ended_defaults_to_last_byte() {
+        let range = parse_range("bytes=900-", 1000).unwrap();
+        assert_eq!(range, ByteRange { start: 900, end: 999 });
+    }
+
+    #[test]
+    fn parse_range_suffix_means_last_n_bytes() {
+        let range = parse_range("bytes=-500", 1000).unwrap();
+        assert_eq!(range, ByteRange { start: 500, end: 999 });
+    }
+
+    #[test]
+    fn parse_range_suffix_larger_than_file_clamps_to_start() {
+        let range = parse_range("bytes=-5000", 1000).unwrap();
+        assert_eq!(range, ByteRange { start: 0, end: 999 });
+    }
+
+    #[test]
+    fn parse_range_rejects_missing_bytes_prefix() {
+        assert!(parse_range("0-499", 1000).is_none());
+    }
+
+    #[test]
+    fn parse_range_rejects_malformed_spec() {
+        assert!(parse_range("bytes=abc-def", 1000).is_none());
+        assert!(parse_range("bytes=100", 1000).is_none());
+    }
+
+    fn write_temp_file(name: &str, contents: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(name);
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn read_range_without_header_returns_full_body() {
+        let path = write_temp_file("mediaflow_test_full.bin", b"hello world");
+        match read_range(&path, None) {
+            RangeBody::Full { bytes, .. } => assert_eq!(bytes, b"hello world"),
+            _ => panic!("expected Full"),
+        }
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn read_range_without_header_caps_large_file_to_partial() {
+        let contents = vec![0u8; (MAX_CHUNK_BYTES + 100) as usize];
+        let path = write_temp_file("mediaflow_test_large_full.bin", &contents);
+        match read_range(&path, None) {
+            RangeBody::Partial { bytes, start, end, total, .. } => {
+                assert_eq!(bytes.len() as u64, MAX_CHUNK_BYTES);
+                assert_eq!((start, end, total), (0, MAX_CHUNK_BYTES - 1, MAX_CHUNK_BYTES + 100));
+            }
+            _ => panic!("expected Partial"),
+        }
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn read_range_open_ended_request_caps_to_max_chunk_bytes() {
+        let contents = vec![0u8; (MAX_CHUNK_BYTES + 100) as usize];
+        let path = write_temp_file("mediaflow_test_large_open_ended.bin", &contents);
+        match read_range(&path, Some("bytes=0-")) {
+            RangeBody::Partial { bytes, end, .. } => {
+                assert_eq!(bytes.len() as u64, MAX_CHUNK_BYTES);
+                assert_eq!(end, MAX_CHUNK_BYTES - 1);
+            }
+            _ => panic!("expected Partial"),
+        }
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn read_range_satisfiable_returns_partial() {
+        let path = write_temp_file("mediaflow_test_partial.bin", b"0123456789");
+        match read_range(&path, Some("bytes=2-4")) {
+            RangeBody::Partial { bytes, start, end, total, .. } => {
+                assert_eq!(bytes, b"234");
+                assert_eq!((start, end, total), (2, 4, 10));
+            }
+            _ => panic!("expected Partial"),
+        }
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn read_range_out_of_bounds_is_unsatisfiable() {
+        let path = write_temp_file("mediaflow_test_416.bin", b"0123456789");
+        match read_range(&path, Some("bytes=5-100")) {
+            RangeBody::Unsatisfiable { total } => assert_eq!(total, 10),
+            _ => panic!("expected Unsatisfiable"),
+        }
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn read_range_missing_file_is_not_found() {
+        let path = std::env::temp_dir().join("mediaflow_test_does_not_exist.bin");
+        let _ = std::fs::remove_file(&path);
+        assert!(matches!(read_range(&path, None), RangeBody::NotFound));
+    }
+}
@@ -0,0 +1,163 @@
+use std::path::Path;
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// Static description of one AI model MediaFlow can use, checksum-verified
+/// the same way [`crate::binaries::install_or_update_yt_dlp`] verifies a
+/// downloaded binary's SHA-256 before trusting it.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelInfo {
+    pub id: &'static str,
+    pub filename: &'static str,
+    pub sha256: &'static str,
+    pub size: u64,
+    pub url: &'static str,
+}
+
+/// Every model the app knows how to install. `download_ai_models`/the
+/// signed updater populate the models directory; `check_models_installed`
+/// scans it against this list.
+pub const MODEL_REGISTRY: &[ModelInfo] = &[ModelInfo {
+    id: "uvr_mdx_net_inst_hq_3",
+    filename: "UVR-MDX-NET-Inst_HQ_3.onnx",
+    sha256: "6b5916d9904691a5a13d1eed55e13ffc0e5be3c362b7d53d34bbb0aa8ebb71d2",
+    size: 62_955_819,
+    url: "https://github.com/TRvlvr/model_repo/releases/download/all_public_uvr_models/UVR-MDX-NET-Inst_HQ_3.onnx",
+}];
+
+/// Install state for one registry entry, as reported by [`scan`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModelState {
+    /// Present and its SHA-256 matches the registry.
+    Installed,
+    /// Present but the wrong size or the wrong hash: a half-downloaded or
+    /// tampered file, not safe to hand to the stem separator.
+    Corrupt,
+    /// Not present on disk at all.
+    Missing,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelStatus {
+    pub id: &'static str,
+    pub filename: &'static str,
+    pub size: u64,
+    pub url: &'static str,
+    pub state: ModelState,
+}
+
+fn sha256_of_file(path: &Path) -> Option<String> {
+    let bytes = std::fs::read(path).ok()?;
+    let mut hasher = Sha256::new();
+    hasher.update(&bytes);
+    Some(hex::encode(hasher.finalize()))
+}
+
+/// Compare every entry in [`MODEL_REGISTRY`] against what's actually on disk
+/// under `models_dir`, reporting missing/corrupt/installed per model so the
+/// UI can offer a per-model install/repair list instead of trusting mere
+/// file existence.
+pub fn scan(models_dir: &Path) -> Vec<ModelStatus> {
+    scan_registry(models_dir, MODEL_REGISTRY)
+}
+
+/// [`scan`]'s actual logic against an arbitrary `registry`, split out so
+/// tests can check the missing/corrupt/installed classification against
+/// known bytes instead of [`MODEL_REGISTRY`]'s real (multi-hundred-megabyte)
+/// entries.
+fn scan_registry(models_dir: &Path, registry: &[ModelInfo]) -> Vec<ModelStatus> {
+    registry
+        .iter()
+        .map(|model| {
+            let path = models_dir.join(model.filename);
+            let state = match std::fs::metadata(&path) {
+                Ok(metadata) if metadata.len() != model.size => ModelState::Corrupt,
+                Ok(_) => match sha256_of_file(&path) {
+                    Some(digest) if digest.eq_ignore_ascii_case(model.sha256) => ModelState::Installed,
+                    _ => ModelState::Corrupt,
+                },
+                Err(_) => ModelState::Missing,
+            };
+            ModelStatus {
+                id: model.id,
+                filename: model.filename,
+                size: model.size,
+                url: model.url,
+                state,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn scan_reports_missing_when_file_absent() {
+        let dir = temp_dir("mediaflow_test_models_missing");
+        let statuses = scan(&dir);
+        assert_eq!(statuses.len(), MODEL_REGISTRY.len());
+        assert_eq!(statuses[0].state, ModelState::Missing);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn scan_reports_corrupt_when_size_mismatches() {
+        let dir = temp_dir("mediaflow_test_models_corrupt_size");
+        let model = &MODEL_REGISTRY[0];
+        std::fs::write(dir.join(model.filename), b"too short").unwrap();
+        let statuses = scan(&dir);
+        assert_eq!(statuses[0].state, ModelState::Corrupt);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn scan_reports_corrupt_when_hash_mismatches_despite_matching_size() {
+        let dir = temp_dir("mediaflow_test_models_corrupt_hash");
+        let bytes = vec![0u8; 16];
+        let registry = [ModelInfo {
+            id: "fixture",
+            filename: "fixture.bin",
+            sha256: "0000000000000000000000000000000000000000000000000000000000000000",
+            size: bytes.len() as u64,
+            url: "https://example.invalid/fixture.bin",
+        }];
+        std::fs::write(dir.join(registry[0].filename), &bytes).unwrap();
+        let statuses = scan_registry(&dir, &registry);
+        assert_eq!(statuses[0].state, ModelState::Corrupt);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn scan_reports_installed_when_hash_matches() {
+        let dir = temp_dir("mediaflow_test_models_installed");
+        let bytes = vec![1u8, 2, 3, 4];
+        let digest = {
+            let mut hasher = Sha256::new();
+            hasher.update(&bytes);
+            hex::encode(hasher.finalize())
+        };
+        let registry = [ModelInfo {
+            id: "fixture",
+            filename: "fixture.bin",
+            sha256: Box::leak(digest.into_boxed_str()),
+            size: bytes.len() as u64,
+            url: "https://example.invalid/fixture.bin",
+        }];
+        std::fs::write(dir.join(registry[0].filename), &bytes).unwrap();
+        let statuses = scan_registry(&dir, &registry);
+        assert_eq!(statuses[0].state, ModelState::Installed);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
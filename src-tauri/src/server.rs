@@ -0,0 +1,195 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use axum::extract::{Path as AxumPath, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response as AxumResponse};
+use axum::routing::get;
+use axum::Router;
+use tauri::http::{Request as TauriRequest, Response as TauriResponse};
+use tokio::sync::Mutex;
+use tower::{Service, ServiceExt};
+
+use crate::media_protocol::{MediaKeyStore, RangeBody};
+
+/// Router behind the `mediaflow-api://` bridge, shared so commands could
+/// register additional routes later without tearing down the server.
+pub type SharedRouter = Arc<Mutex<Router>>;
+
+#[derive(Clone)]
+struct ApiState {
+    media: Arc<MediaKeyStore>,
+}
+
+/// Build the axum router exposing every media key registered in
+/// [`MediaKeyStore`] as a uniform HTTP surface: `/stream/:id` for
+/// video/audio playback, `/stems/:job/:stem` for `separate_stems` output,
+/// and `/thumbnail/:id` for preview art.
+pub fn build_router(media: Arc<MediaKeyStore>) -> Router {
+    Router::new()
+        .route("/stream/:id", get(stream_media))
+        .route("/stems/:job/:stem", get(stream_stem))
+        .route("/thumbnail/:id", get(stream_media))
+        .with_state(ApiState { media })
+}
+
+async fn stream_media(
+    State(state): State<ApiState>,
+    AxumPath(id): AxumPath<String>,
+    headers: HeaderMap,
+) -> AxumResponse {
+    match state.media.path_for(&id) {
+        Some(path) => serve_path(&path, range_header(&headers)),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+async fn stream_stem(
+    State(state): State<ApiState>,
+    AxumPath((job, stem)): AxumPath<(String, String)>,
+    headers: HeaderMap,
+) -> AxumResponse {
+    let Some(dir) = state.media.path_for(&job) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    match safe_join(&dir, &stem) {
+        Some(path) => serve_path(&path, range_header(&headers)),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+fn range_header(headers: &HeaderMap) -> Option<&str> {
+    headers.get(header::RANGE).and_then(|value| value.to_str().ok())
+}
+
+/// Join `stem` onto `dir` and verify the result is still a descendant of
+/// `dir` once canonicalized, so a path-traversal `stem` (a `/`- or
+/// `..`-containing segment, which axum's `Path<String>` happily hands us
+/// percent-decoded) can't escape the stems directory this route is scoped
+/// to and read arbitrary files off disk.
+fn safe_join(dir: &Path, stem: &str) -> Option<PathBuf> {
+    if stem.contains('/') || stem.contains('\\') || stem == ".." {
+        return None;
+    }
+
+    let candidate = dir.join(stem);
+    let canonical_dir = dir.canonicalize().ok()?;
+    let canonical_candidate = candidate.canonicalize().ok()?;
+
+    if canonical_candidate.starts_with(&canonical_dir) {
+        Some(candidate)
+    } else {
+        None
+    }
+}
+
+/// Serve `path` with HTTP range support, sharing [`media_protocol::read_range`]
+/// with the `mediaflow://` scheme handler so `<video>`/`<audio>` elements can
+/// scrub this surface too instead of always downloading the full body.
+fn serve_path(path: &Path, range_header: Option<&str>) -> AxumResponse {
+    match crate::media_protocol::read_range(path, range_header) {
+        RangeBody::NotFound => StatusCode::NOT_FOUND.into_response(),
+        RangeBody::Full { mime, bytes } => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, mime.to_string()), (header::ACCEPT_RANGES, "bytes".to_string())],
+            bytes,
+        )
+            .into_response(),
+        RangeBody::Partial { mime, bytes, start, end, total } => (
+            StatusCode::PARTIAL_CONTENT,
+            [
+                (header::CONTENT_TYPE, mime.to_string()),
+                (header::ACCEPT_RANGES, "bytes".to_string()),
+                (header::CONTENT_RANGE, format!("bytes {start}-{end}/{total}")),
+            ],
+            bytes,
+        )
+            .into_response(),
+        RangeBody::Unsatisfiable { total } => (
+            StatusCode::RANGE_NOT_SATISFIABLE,
+            [(header::CONTENT_RANGE, format!("bytes */{total}"))],
+        )
+            .into_response(),
+    }
+}
+
+/// Bridge a Tauri HTTP request into the embedded axum router and convert
+/// its response back into a `tauri::http::Response`, the way Krys4lide
+/// drives `tauri::http::Request<Vec<u8>>` through `axum::Router::as_service`.
+pub async fn handle(router: SharedRouter, request: TauriRequest<Vec<u8>>) -> TauriResponse<Vec<u8>> {
+    let (parts, body) = request.into_parts();
+    let axum_request = axum::extract::Request::from_parts(parts, axum::body::Body::from(body));
+
+    let mut router = router.lock().await;
+    let ready = match router.ready().await {
+        Ok(service) => service,
+        Err(_) => return empty_response(StatusCode::INTERNAL_SERVER_ERROR),
+    };
+
+    let response = match ready.call(axum_request).await {
+        Ok(response) => response,
+        Err(_) => return empty_response(StatusCode::INTERNAL_SERVER_ERROR),
+    };
+
+    let (parts, body) = response.into_parts();
+    let bytes = axum::body::to_bytes(body, usize::MAX)
+        .await
+        .map(|b| b.to_vec())
+        .unwrap_or_default();
+
+    TauriResponse::from_parts(parts, bytes)
+}
+
+fn empty_response(status: StatusCode) -> TauriResponse<Vec<u8>> {
+    TauriResponse::builder().status(status).body(Vec::new()).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn safe_join_resolves_plain_stem() {
+        let dir = temp_dir("mediaflow_test_server_safe_join_plain");
+        std::fs::write(dir.join("drums.wav"), b"x").unwrap();
+        assert_eq!(safe_join(&dir, "drums.wav"), Some(dir.join("drums.wav")));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn safe_join_rejects_slash_and_parent_traversal() {
+        let dir = temp_dir("mediaflow_test_server_safe_join_traversal");
+        assert_eq!(safe_join(&dir, "../secret"), None);
+        assert_eq!(safe_join(&dir, "sub/drums.wav"), None);
+        assert_eq!(safe_join(&dir, ".."), None);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn safe_join_rejects_nonexistent_stem() {
+        let dir = temp_dir("mediaflow_test_server_safe_join_missing");
+        assert_eq!(safe_join(&dir, "missing.wav"), None);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn range_header_reads_range_value() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::RANGE, "bytes=0-99".parse().unwrap());
+        assert_eq!(range_header(&headers), Some("bytes=0-99"));
+    }
+
+    #[test]
+    fn range_header_missing_is_none() {
+        let headers = HeaderMap::new();
+        assert_eq!(range_header(&headers), None);
+    }
+}
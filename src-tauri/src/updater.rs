@@ -0,0 +1,216 @@
+use std::path::{Path, PathBuf};
+
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+
+/// Embedded public key (hex-encoded, 32-byte ed25519) models/tools are
+/// signed against. Rotating this requires shipping an app update, which is
+/// the point: it's the one trust anchor out-of-band manifest updates can't
+/// forge their way around.
+const MANIFEST_SIGNING_KEY_HEX: &str =
+    "8b93e46011eaaf0fae32fc5ffe36b5d1e47a3ace83aa2a52bac30f9efec91d5a";
+
+/// One entry in the signed update manifest: a downloadable artifact (AI
+/// model or bundled tool) plus the detached ed25519 signature over its raw
+/// bytes, hex-encoded the same way `binaries::sha256_hex` encodes checksums.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ManifestEntry {
+    pub id: String,
+    pub url: String,
+    pub signature: String,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Manifest {
+    pub entries: Vec<ManifestEntry>,
+}
+
+/// Directory the updater installs verified artifacts into and records
+/// installed versions in, alongside the existing AI-models directory.
+fn updates_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let mut dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|err| format!("Unable to resolve the app data directory: {err}"))?;
+    dir.push("updates");
+    std::fs::create_dir_all(&dir).map_err(|err| format!("Failed to create updates directory: {err}"))?;
+    Ok(dir)
+}
+
+fn installed_versions_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    Ok(updates_dir(app)?.join("installed.json"))
+}
+
+fn read_installed_versions(app: &tauri::AppHandle) -> serde_json::Value {
+    let Ok(path) = installed_versions_path(app) else {
+        return serde_json::json!({});
+    };
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_else(|| serde_json::json!({}))
+}
+
+fn record_installed_version(app: &tauri::AppHandle, id: &str, version: &str) -> Result<(), String> {
+    let path = installed_versions_path(app)?;
+    let mut versions = read_installed_versions(app);
+    versions[id] = serde_json::json!(version);
+    let text = serde_json::to_string_pretty(&versions).map_err(|err| err.to_string())?;
+    std::fs::write(path, text).map_err(|err| format!("Failed to record installed version: {err}"))
+}
+
+fn verify_signature(bytes: &[u8], signature_hex: &str) -> Result<(), String> {
+    let key_bytes: [u8; 32] = hex::decode(MANIFEST_SIGNING_KEY_HEX)
+        .map_err(|err| format!("Invalid embedded signing key: {err}"))?
+        .try_into()
+        .map_err(|_| "Embedded signing key is not 32 bytes".to_string())?;
+    let verifying_key =
+        VerifyingKey::from_bytes(&key_bytes).map_err(|err| format!("Invalid embedded signing key: {err}"))?;
+
+    let sig_bytes: [u8; 64] = hex::decode(signature_hex.trim())
+        .map_err(|err| format!("Malformed signature: {err}"))?
+        .try_into()
+        .map_err(|_| "Signature is not 64 bytes".to_string())?;
+    let signature = Signature::from_bytes(&sig_bytes);
+
+    verifying_key
+        .verify(bytes, &signature)
+        .map_err(|_| "Signature verification failed".to_string())
+}
+
+/// The signature only covers the artifact bytes, not `id` — a manifest host
+/// could pair a legitimately-signed artifact with a traversal `id` like
+/// `"../../../.config/autostart/x.desktop"` to control *where* those signed
+/// bytes land. Reject anything that isn't a single plain path component.
+fn sanitize_entry_id(id: &str) -> Result<(), String> {
+    if id.is_empty() || id.contains('/') || id.contains('\\') || id == ".." || id == "." {
+        return Err(format!("Refusing unsafe manifest entry id: {id:?}"));
+    }
+    Ok(())
+}
+
+/// Download `entry`'s artifact, verify its detached ed25519 signature
+/// against [`MANIFEST_SIGNING_KEY_HEX`], and move it into `dest_dir` under
+/// its `id`. The partially downloaded file is deleted on signature failure
+/// so a tampered or corrupt download is never left in place to be picked up
+/// by a later run.
+async fn install_entry(entry: &ManifestEntry, dest_dir: &Path) -> Result<(), String> {
+    sanitize_entry_id(&entry.id)?;
+
+    let bytes = reqwest::get(&entry.url)
+        .await
+        .map_err(|err| format!("Failed to download {}: {err}", entry.url))?
+        .bytes()
+        .await
+        .map_err(|err| format!("Failed to read response body from {}: {err}", entry.url))?;
+
+    let dest = dest_dir.join(&entry.id);
+
+    if let Err(err) = verify_signature(&bytes, &entry.signature) {
+        return Err(format!("Refusing to install {}: {err}", entry.id));
+    }
+
+    tokio::fs::write(&dest, &bytes)
+        .await
+        .map_err(|err| format!("Failed to write {}: {err}", dest.display()))?;
+
+    Ok(())
+}
+
+/// Fetch `manifest_url`, locate the entry with id `entry_id`, verify its
+/// signature against [`MANIFEST_SIGNING_KEY_HEX`], and return the verified
+/// bytes without writing anything under `updates_dir`. Shared by
+/// [`apply_manifest`] (which installs every entry under its `id`) and
+/// callers like `binaries::install_or_update_ffmpeg` that need one specific
+/// signed artifact placed somewhere else entirely — this is how ffmpeg gets
+/// self-managed despite upstream not publishing its own checksums.
+pub(crate) async fn fetch_verified_entry(manifest_url: &str, entry_id: &str) -> Result<Vec<u8>, String> {
+    let manifest: Manifest = reqwest::get(manifest_url)
+        .await
+        .map_err(|err| format!("Failed to fetch manifest from {manifest_url}: {err}"))?
+        .json()
+        .await
+        .map_err(|err| format!("Malformed manifest at {manifest_url}: {err}"))?;
+
+    let entry = manifest
+        .entries
+        .iter()
+        .find(|entry| entry.id == entry_id)
+        .ok_or_else(|| format!("No \"{entry_id}\" entry in manifest at {manifest_url}"))?;
+
+    sanitize_entry_id(&entry.id)?;
+
+    let bytes = reqwest::get(&entry.url)
+        .await
+        .map_err(|err| format!("Failed to download {}: {err}", entry.url))?
+        .bytes()
+        .await
+        .map_err(|err| format!("Failed to read response body from {}: {err}", entry.url))?;
+
+    verify_signature(&bytes, &entry.signature).map_err(|err| format!("Refusing to use {entry_id}: {err}"))?;
+
+    Ok(bytes.to_vec())
+}
+
+/// Fetch the signed manifest from `manifest_url`, install every entry whose
+/// signature checks out, and record its version. Returns the ids that were
+/// installed. A single bad entry fails that entry only; the rest of the
+/// manifest still installs.
+pub async fn apply_manifest(app: &tauri::AppHandle, manifest_url: &str) -> Result<Vec<String>, String> {
+    let manifest: Manifest = reqwest::get(manifest_url)
+        .await
+        .map_err(|err| format!("Failed to fetch manifest from {manifest_url}: {err}"))?
+        .json()
+        .await
+        .map_err(|err| format!("Malformed manifest at {manifest_url}: {err}"))?;
+
+    let dest_dir = updates_dir(app)?;
+    let mut installed = Vec::new();
+    let mut errors = Vec::new();
+
+    for entry in &manifest.entries {
+        match install_entry(entry, &dest_dir).await {
+            Ok(()) => {
+                record_installed_version(app, &entry.id, &entry.version)?;
+                installed.push(entry.id.clone());
+            }
+            Err(err) => errors.push(err),
+        }
+    }
+
+    if installed.is_empty() && !errors.is_empty() {
+        return Err(errors.join("; "));
+    }
+
+    Ok(installed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_entry_id_accepts_plain_filename() {
+        assert!(sanitize_entry_id("UVR-MDX-NET-Inst_HQ_3.onnx").is_ok());
+    }
+
+    #[test]
+    fn sanitize_entry_id_rejects_path_traversal() {
+        assert!(sanitize_entry_id("../../../.config/autostart/x.desktop").is_err());
+        assert!(sanitize_entry_id("..").is_err());
+        assert!(sanitize_entry_id(".").is_err());
+    }
+
+    #[test]
+    fn sanitize_entry_id_rejects_path_separators() {
+        assert!(sanitize_entry_id("sub/dir/model.onnx").is_err());
+        assert!(sanitize_entry_id("sub\\dir\\model.onnx").is_err());
+    }
+
+    #[test]
+    fn sanitize_entry_id_rejects_empty_id() {
+        assert!(sanitize_entry_id("").is_err());
+    }
+}